@@ -0,0 +1,39 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use shamir_bip39::{Bip39Dictionary, Bip39Secret, Language};
+
+/// Valid mnemonics of 12 and 24 words.
+const MNEMONICS: [&str; 2] = [
+    "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    "motion domain employ liberty priority moral boil property urge error chunk pave bullet \
+    blanket bind adapt local enroll bullet permit theory vibrant initial venue",
+];
+
+fn mnemonic(c: &mut Criterion) {
+    let dictionary = Bip39Dictionary::builtin(Language::English).unwrap();
+    let mut group = c.benchmark_group("mnemonic");
+    group.throughput(Throughput::Elements(1));
+    for mnemonic in MNEMONICS {
+        let words = mnemonic.split_whitespace().count();
+        let secret = Bip39Secret::from_mnemonic(mnemonic, &dictionary).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("from_mnemonic", words),
+            mnemonic,
+            |b, mnemonic| {
+                b.iter(|| Bip39Secret::from_mnemonic(black_box(mnemonic), &dictionary).unwrap())
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("to_mnemonic", words),
+            &secret,
+            |b, secret| b.iter(|| black_box(secret).to_mnemonic(&dictionary)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mnemonic);
+criterion_main!(benches);
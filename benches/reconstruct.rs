@@ -0,0 +1,43 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use shamir_bip39::{FieldArray, Gf256, ShamirSecretSharing, ShamirShare};
+
+/// The number of bytes of the entropy of a 24-word mnemonic.
+const BYTES: usize = 32;
+
+/// Reconstruct each byte of the secret separately (recomputing the Lagrange coefficients for
+/// every byte), as a baseline.
+fn reconstruct_bytes(shares: &[ShamirShare<FieldArray<Gf256, BYTES>>]) -> [Gf256; BYTES] {
+    std::array::from_fn(|i| {
+        let bytes = shares
+            .iter()
+            .map(|share| ShamirShare::new(*share.id(), *share.secret().iter().nth(i).unwrap()))
+            .collect::<Vec<_>>();
+        Gf256::reconstruct(&bytes).unwrap()
+    })
+}
+
+fn reconstruct(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("reconstruct");
+    for t in [2, 3, 16, 64, 255] {
+        let mut bytes = [0; BYTES];
+        rng.fill_bytes(&mut bytes);
+        let secret = FieldArray::from(bytes.map(Gf256::from));
+        let shares = secret.split(t, t, &mut rng).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("per-byte", t), &shares, |b, shares| {
+            b.iter(|| reconstruct_bytes(black_box(shares)))
+        });
+        group.bench_with_input(BenchmarkId::new("field-array", t), &shares, |b, shares| {
+            b.iter(|| FieldArray::reconstruct(black_box(shares)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, reconstruct);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use shamir_bip39::{FieldArray, Gf256, ShamirSecretSharing, ShamirShare};
+
+/// The number of bytes of the entropy of a 24-word mnemonic.
+const BYTES: usize = 32;
+
+/// Split each byte of the secret separately and regroup the bytes of each share, as a baseline.
+fn split_bytes<R: rand::CryptoRng + RngCore>(
+    secret: &FieldArray<Gf256, BYTES>,
+    n: u8,
+    t: u8,
+    rng: &mut R,
+) -> Vec<ShamirShare<FieldArray<Gf256, BYTES>>> {
+    let mut bytes = HashMap::new();
+    for element in secret.iter() {
+        for share in element.split(n, t, rng).unwrap() {
+            let (id, byte) = share.into_inner();
+            bytes.entry(id).or_insert_with(Vec::new).push(byte);
+        }
+    }
+    let mut shares = bytes
+        .into_iter()
+        .map(|(id, bytes)| {
+            let array = std::array::from_fn(|i| bytes[i]);
+            ShamirShare::new(id, FieldArray::from(array))
+        })
+        .collect::<Vec<_>>();
+    shares.sort_by_key(|share| *share.id());
+    shares
+}
+
+fn split(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut bytes = [0; BYTES];
+    rng.fill_bytes(&mut bytes);
+    let secret = FieldArray::from(bytes.map(Gf256::from));
+
+    let mut group = c.benchmark_group("split");
+    for (n, t) in [(3, 2), (16, 8), (255, 128)] {
+        let parameters = format!("{t}-of-{n}");
+        group.bench_function(BenchmarkId::new("per-byte", &parameters), |b| {
+            b.iter(|| split_bytes(black_box(&secret), n, t, &mut rng))
+        });
+        group.bench_function(BenchmarkId::new("field-array", &parameters), |b| {
+            b.iter(|| black_box(&secret).split(n, t, &mut rng).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, split);
+criterion_main!(benches);
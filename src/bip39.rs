@@ -1,143 +1,542 @@
 // Copyright (c) Alberto Sonnino
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{array::TryFromSliceError, fmt::Debug, fs::read_to_string, path::Path};
+use std::{array, collections::HashMap, fmt::Debug, fs::read_to_string, io, mem, path::Path};
 
-use eyre::{ensure, eyre, Result};
 use fastcrypto::hash::{HashFunction, Sha256};
-use gf256::gf256;
 use rand::{CryptoRng, RngCore};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::{
-    shamir::{FieldArray, ShamirSecretSharing, ShamirShare},
-    utils::{bits_to_bytes, bytes_to_bits},
+    commitment::{self, PolynomialCommitment, ShareProof},
+    error::{Error, Result},
+    gf256::Gf256,
+    memory,
+    shamir::{check_shares, FieldArray, ShamirSecretSharing, ShamirShare, Zero},
+    utils::{bits_to_bytes, bytes_to_bits, normalize, typo_distance},
 };
 
-/// Parameters of the bip-39 specification (24 words variant).
+/// Parameters of the bip-39 specification.
 const DICTIONARY_INDICES_BITS: usize = 11;
-const MNEMONIC_WORDS: usize = 24;
 const DICTIONARY_WORDS: usize = 2 << (DICTIONARY_INDICES_BITS - 1);
-const CHECKSUM_BITS: usize = (MNEMONIC_WORDS * DICTIONARY_INDICES_BITS) / 33;
-const ENTROPY_BITS: usize = CHECKSUM_BITS * 32;
-const ENTROPY_BYTES: usize = ENTROPY_BITS / 8;
+
+/// The minimum number of letters of a word prefix to be expanded into the full word.
+const WORD_PREFIX_LETTERS: usize = 4;
+
+/// The maximum number of suggestions for a misspelled word, and their maximum edit distance.
+const MAX_SUGGESTIONS: usize = 5;
+const MAX_SUGGESTION_EDITS: usize = 2;
+
+/// The maximum number of misspelled words of a mnemonic that can be corrected.
+const MAX_CORRECTED_WORDS: usize = 3;
+
+/// The placeholder of a missing word in a mnemonic, the maximum number of missing words that can
+/// be recovered, and the number of candidate mnemonics tried between two progress reports.
+const MISSING_WORD: &str = "?";
+const MAX_MISSING_WORDS: usize = 2;
+const RECOVERY_PROGRESS_INTERVAL: usize = 1 << 20;
+
+/// The default maximum number of candidate mnemonics to search when recovering missing words,
+/// enough for two missing words at unknown positions of a 24-word mnemonic.
+pub const MAX_RECOVERY_CANDIDATES: usize = 1 << 31;
+
+/// The mnemonic lengths (in words) allowed by the bip-39 specification.
+const MNEMONIC_WORDS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// The number of checksum bits of a mnemonic with the given number of words.
+const fn checksum_bits(mnemonic_words: usize) -> usize {
+    (mnemonic_words * DICTIONARY_INDICES_BITS) / 33
+}
+
+/// The number of entropy bits of a mnemonic with the given number of words.
+pub const fn entropy_bits(mnemonic_words: usize) -> usize {
+    checksum_bits(mnemonic_words) * 32
+}
+
+/// The languages of the official bip-39 wordlists, embedded in the binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Japanese,
+    Korean,
+    Spanish,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Czech,
+    Portuguese,
+}
+
+impl Language {
+    /// All the languages, in the order of the official wordlists.
+    pub const ALL: [Self; 10] = [
+        Self::English,
+        Self::Japanese,
+        Self::Korean,
+        Self::Spanish,
+        Self::ChineseSimplified,
+        Self::ChineseTraditional,
+        Self::French,
+        Self::Italian,
+        Self::Czech,
+        Self::Portuguese,
+    ];
+
+    /// The official wordlist of the language and its (hex-encoded) SHA-256 digest.
+    fn wordlist(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::English => (
+                include_str!("../assets/bip39-en.txt"),
+                "2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda",
+            ),
+            Self::Japanese => (
+                include_str!("../assets/bip39-ja.txt"),
+                "2eed0aef492291e061633d7ad8117f1a2b03eb80a29d0e4e3117ac2528d05ffd",
+            ),
+            Self::Korean => (
+                include_str!("../assets/bip39-ko.txt"),
+                "9e95f86c167de88f450f0aaf89e87f6624a57f973c67b516e338e8e8b8897f60",
+            ),
+            Self::Spanish => (
+                include_str!("../assets/bip39-es.txt"),
+                "46846a5a0139d1e3cb77293e521c2865f7bcdb82c44e8d0a06a2cd0ecba48c0b",
+            ),
+            Self::ChineseSimplified => (
+                include_str!("../assets/bip39-zh-hans.txt"),
+                "5c5942792bd8340cb8b27cd592f1015edf56a8c5b26276ee18a482428e7c5726",
+            ),
+            Self::ChineseTraditional => (
+                include_str!("../assets/bip39-zh-hant.txt"),
+                "417b26b3d8500a4ae3d59717d7011952db6fc2fb84b807f3f94ac734e89c1b5f",
+            ),
+            Self::French => (
+                include_str!("../assets/bip39-fr.txt"),
+                "ebc3959ab7801a1df6bac4fa7d970652f1df76b683cd2f4003c941c63d517e59",
+            ),
+            Self::Italian => (
+                include_str!("../assets/bip39-it.txt"),
+                "d392c49fdb700a24cd1fceb237c1f65dcc128f6b34a8aacb58b59384b5c648c2",
+            ),
+            Self::Czech => (
+                include_str!("../assets/bip39-cs.txt"),
+                "7e80e161c3e93d9554c2efb78d4e3cebf8fc727e9c52e03b83b94406bdcc95fc",
+            ),
+            Self::Portuguese => (
+                include_str!("../assets/bip39-pt.txt"),
+                "2685e9c194c82ae67e10ba59d9ea5345a23dc093e92276fc5361f6667d79cd3f",
+            ),
+        }
+    }
+
+    /// Detect the language of the only official wordlist containing all the words of the given
+    /// mnemonics (or the most words, if some are misspelled). Fail if the mnemonics do not all
+    /// fit the same wordlist, or if the words fit several wordlists (e.g., some words are both in
+    /// the English and French lists).
+    pub fn detect<'a, I: IntoIterator<Item = &'a str>>(mnemonics: I) -> Result<Self> {
+        let dictionaries = Self::ALL
+            .iter()
+            .map(|language| Ok((*language, Bip39Dictionary::builtin(*language)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut candidates = Self::ALL.to_vec();
+        for (i, mnemonic) in mnemonics.into_iter().enumerate() {
+            // Tolerate misspelled words by picking the wordlists containing the most words.
+            let counts = dictionaries
+                .iter()
+                .map(|(language, dictionary)| {
+                    let words = mnemonic.split_whitespace();
+                    (*language, words.filter(|w| dictionary.contains(w)).count())
+                })
+                .collect::<Vec<_>>();
+            let best = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+            if best == 0 {
+                return Err(Error::UnknownLanguage { mnemonic: i });
+            }
+            let matches = counts
+                .into_iter()
+                .filter(|(_, count)| *count == best)
+                .map(|(language, _)| language)
+                .collect::<Vec<_>>();
+
+            candidates.retain(|language| matches.contains(language));
+            if candidates.is_empty() {
+                return Err(Error::MixedLanguages);
+            }
+        }
+
+        match candidates.as_slice() {
+            [language] => Ok(*language),
+            _ => Err(Error::AmbiguousLanguage(candidates)),
+        }
+    }
+
+    /// The separator between the words of a mnemonic (Japanese uses the ideographic space).
+    fn separator(&self) -> &'static str {
+        match self {
+            Self::Japanese => "\u{3000}",
+            _ => " ",
+        }
+    }
+}
 
 /// The bip-39 dictionary.
 pub struct Bip39Dictionary {
+    /// The words of the dictionary (NFKD-normalized).
     words: [String; DICTIONARY_WORDS],
+    /// The index of each word of the dictionary.
+    indices: HashMap<String, u16>,
+    /// The (hex-encoded) SHA-256 digest of the dictionary file.
+    digest: String,
+    /// The separator between the words of a mnemonic.
+    separator: &'static str,
 }
 
 impl Bip39Dictionary {
-    /// Load the bip-39 dictionary from a file.
+    /// Load the official bip-39 dictionary of the given language embedded in the binary. Fail
+    /// if the embedded wordlist does not match the digest of the official list.
+    pub fn builtin(language: Language) -> Result<Self> {
+        let (wordlist, expected) = language.wordlist();
+        let dictionary = Self::parse(wordlist, language.separator())?;
+        if dictionary.digest() != expected {
+            return Err(Error::CorruptedDictionary(language));
+        }
+        Ok(dictionary)
+    }
+
+    /// Load the bip-39 dictionary from a file. Fail if the file does not contain exactly 2048
+    /// distinct words (one per line) without whitespace. Use [`Self::warnings`] to check the
+    /// dictionary for less severe issues.
     pub fn load<P: AsRef<Path>>(dictionary_path: P) -> Result<Self> {
-        let words = read_to_string(dictionary_path)?
+        Self::parse(&read_to_string(dictionary_path)?, " ")
+    }
+
+    /// Parse a bip-39 dictionary with one word per line.
+    fn parse(content: &str, separator: &'static str) -> Result<Self> {
+        let words = content
             .lines()
-            .map(Into::into)
+            .map(|w| w.nfkd().collect())
             .collect::<Vec<_>>();
         let length = words.len();
+        let words: [String; DICTIONARY_WORDS] =
+            words
+                .try_into()
+                .map_err(|_| Error::InvalidDictionaryLength {
+                    expected: DICTIONARY_WORDS,
+                    actual: length,
+                })?;
+
+        let mut indices = HashMap::new();
+        for (i, word) in (0..).zip(&words) {
+            let line = i as usize + 1;
+            if word.is_empty() {
+                return Err(Error::EmptyDictionaryWord { line });
+            }
+            if word.contains(char::is_whitespace) {
+                let word = word.clone();
+                return Err(Error::DictionaryWordWithWhitespace { line, word });
+            }
+            if let Some(previous) = indices.insert(word.clone(), i) {
+                return Err(Error::DuplicateDictionaryWord {
+                    first_line: previous as usize + 1,
+                    line,
+                    word: word.clone(),
+                });
+            }
+        }
 
+        let digest = Sha256::digest(content.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
         Ok(Self {
-            words: words.try_into().map_err(|_| {
-                eyre!("Invalid BIP-39 dictionary length {length} != {DICTIONARY_WORDS}")
-            })?,
+            words,
+            indices,
+            digest,
+            separator,
         })
     }
 
-    /// Get the index of a word in the dictionary (as bits).
+    /// The separator between the words of a mnemonic.
+    pub fn separator(&self) -> &str {
+        self.separator
+    }
+
+    /// The (hex-encoded) SHA-256 digest of the dictionary file, to record which dictionary was
+    /// used to encode a mnemonic.
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    /// Check the dictionary for issues that do not prevent encoding mnemonics but suggest it is
+    /// not a valid bip-39 wordlist: words that are not identified by their first four letters
+    /// (which prevents abbreviating them) and words that are not sorted.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let shared = self.shared_stems();
+        if let Some(example) = shared.first() {
+            warnings.push(format!(
+                "{} groups of words share their first four letters (e.g., {})",
+                shared.len(),
+                example.join(", ")
+            ));
+        }
+
+        let unsorted = self.words.windows(2).position(|w| w[0] >= w[1]);
+        if let Some(i) = unsorted {
+            warnings.push(format!(
+                "The words are not sorted (e.g., '{}' comes before '{}')",
+                self.words[i],
+                self.words[i + 1]
+            ));
+        }
+        warnings
+    }
+
+    /// Check whether each word is identified by its stem (its first four letters), so that
+    /// mnemonics can be written as stems.
+    pub fn has_unique_stems(&self) -> bool {
+        self.shared_stems().is_empty()
+    }
+
+    /// The (sorted) groups of words sharing the same stem.
+    fn shared_stems(&self) -> Vec<Vec<&str>> {
+        let mut stems = HashMap::<_, Vec<_>>::new();
+        for word in &self.words {
+            stems
+                .entry(Self::stem(word))
+                .or_default()
+                .push(word.as_str());
+        }
+        let mut shared = stems
+            .into_values()
+            .filter(|words| words.len() > 1)
+            .collect::<Vec<_>>();
+        shared.sort();
+        shared
+    }
+
+    /// Check whether the dictionary contains a word (or an unambiguous prefix of a word).
+    pub fn contains(&self, word: &str) -> bool {
+        matches!(self.find(word), Ok(Some(_)))
+    }
+
+    /// Get the index of a word in the dictionary (as bits). The word may also be abbreviated to
+    /// any prefix of at least four letters that identifies a single word of the dictionary.
     pub fn bits_from_word(&self, word: &str) -> Result<[bool; DICTIONARY_INDICES_BITS]> {
-        let index = self
+        let index = self.index_of(word)?;
+        Ok(array::from_fn(|i| {
+            (index >> (DICTIONARY_INDICES_BITS - 1 - i)) & 1 == 1
+        }))
+    }
+
+    /// Get the index of a word (or of an unambiguous prefix of a word) in the dictionary. Fail
+    /// with the closest dictionary words if the word is not in the dictionary.
+    fn index_of(&self, word: &str) -> Result<u16> {
+        match self.find(word)? {
+            Some(index) => Ok(index),
+            None => Err(Error::InvalidWord {
+                index: None,
+                word: word.to_string(),
+                suggestions: self
+                    .suggestions(word)
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Find the index of a word (or of an unambiguous prefix of a word) in the dictionary. The
+    /// word is NFKD-normalized before the lookup. Fail if the word is an ambiguous prefix.
+    fn find(&self, word: &str) -> Result<Option<u16>> {
+        let normalized = normalize(word);
+        if let Some(index) = self.indices.get(normalized.as_str()) {
+            return Ok(Some(*index));
+        }
+
+        let letters = normalized.chars().filter(|c| c.is_alphabetic()).count();
+        let candidates = (0..)
+            .zip(&self.words)
+            .filter(|(_, w)| letters >= WORD_PREFIX_LETTERS && w.starts_with(normalized.as_str()))
+            .collect::<Vec<_>>();
+
+        match candidates.as_slice() {
+            [] => Ok(None),
+            [(index, _)] => Ok(Some(*index)),
+            _ => {
+                let matches = candidates.iter().map(|(_, w)| w.to_string()).collect();
+                Err(Error::AmbiguousWord {
+                    index: None,
+                    word: word.to_string(),
+                    matches,
+                })
+            }
+        }
+    }
+
+    /// Get the words of the dictionary closest to a (misspelled) word, ranked by edit distance.
+    /// Substituting neighbouring keys of a keyboard counts as half an edit, and words more than
+    /// two edits away are never suggested.
+    pub fn suggestions(&self, word: &str) -> Vec<&str> {
+        let normalized = normalize(word);
+        let mut candidates = self
             .words
             .iter()
-            .position(|w| w == word)
-            .ok_or(eyre!("Invalid BIP-39 word '{word}' in mnemonic"))?;
-        let bits = bytes_to_bits(&index.to_be_bytes());
-        Ok(bits[bits.len() - DICTIONARY_INDICES_BITS..]
-            .try_into()
-            .expect("Slice size should match the dictionary index bit length"))
+            .map(|w| (typo_distance(&normalized, w), w.as_str()))
+            .filter(|(distance, _)| *distance <= 2 * MAX_SUGGESTION_EDITS)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(distance, _)| *distance);
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, w)| w)
+            .collect()
+    }
+
+    /// Get the stem of a word: its first four letters (which identify the word in the official
+    /// English wordlist).
+    pub fn stem(word: &str) -> &str {
+        let mut letters = 0;
+        let end = word
+            .char_indices()
+            .find(|(_, c)| {
+                if c.is_alphabetic() {
+                    letters += 1;
+                }
+                letters > WORD_PREFIX_LETTERS
+            })
+            .map_or(word.len(), |(i, _)| i);
+        &word[..end]
+    }
+
+    /// Get the word at a given index (as bits) in the dictionary.
+    pub fn word_from_bits(&self, bits: &[bool; DICTIONARY_INDICES_BITS]) -> &str {
+        self.word(Self::index_from_bits(bits))
     }
 
     /// Get the word at a given index in the dictionary.
-    pub fn word_from_bits(&self, bits: &[bool; DICTIONARY_INDICES_BITS]) -> String {
-        let mut extended = bytes_to_bits(&usize::to_be_bytes(0));
-        let length = extended.len();
-        extended[length - DICTIONARY_INDICES_BITS..].copy_from_slice(bits);
-        let bytes = bits_to_bytes(&extended)
-            .try_into()
-            .expect("Slice size should match the dictionary index byte length");
-        let index = usize::from_be_bytes(bytes);
-        self.words[index].clone()
+    pub fn word(&self, index: u16) -> &str {
+        &self.words[index as usize]
+    }
+
+    /// Convert the bits of a dictionary index into the index.
+    fn index_from_bits(bits: &[bool; DICTIONARY_INDICES_BITS]) -> u16 {
+        bits.iter().fold(0, |index, bit| (index << 1) | *bit as u16)
     }
 }
 
-/// The entropy of a bip-39 secret.
+/// The entropy of a bip-39 secret (128 to 256 bits, in steps of 32 bits). The bits are wiped
+/// when the entropy is dropped.
 #[derive(PartialEq, Eq)]
 #[cfg_attr(test, derive(Debug, Clone))]
-struct Entropy([bool; ENTROPY_BITS]);
+struct Entropy(Vec<bool>);
 
 impl Entropy {
     pub fn as_bits(&self) -> &[bool] {
         &self.0
     }
 
-    pub fn to_bytes(&self) -> [u8; ENTROPY_BYTES] {
-        bits_to_bytes(&self.0).try_into().unwrap()
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(bits_to_bytes(&self.0))
     }
 
-    #[cfg(test)]
-    pub fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+    /// The number of words of the mnemonic encoding this entropy.
+    pub fn mnemonic_words(&self) -> usize {
+        (self.0.len() + self.0.len() / 32) / DICTIONARY_INDICES_BITS
+    }
+
+    /// Draw the entropy of a mnemonic of `mnemonic_words` words from a random number generator.
+    pub fn random<R: CryptoRng + RngCore>(mnemonic_words: usize, rng: &mut R) -> Self {
         use rand::Rng;
 
-        Self(std::array::from_fn(|_| rng.random()))
+        Self(
+            (0..entropy_bits(mnemonic_words))
+                .map(|_| rng.random())
+                .collect(),
+        )
+    }
+}
+
+impl Drop for Entropy {
+    fn drop(&mut self) {
+        self.0.zeroize();
     }
 }
 
 impl TryFrom<&[bool]> for Entropy {
-    type Error = TryFromSliceError;
+    type Error = Error;
 
-    fn try_from(value: &[bool]) -> Result<Self, Self::Error> {
-        Ok(Self(value.try_into()?))
+    fn try_from(value: &[bool]) -> Result<Self> {
+        let length = value.len();
+        if !MNEMONIC_WORDS.iter().any(|&w| entropy_bits(w) == length) {
+            return Err(Error::InvalidEntropyLength { actual: length });
+        }
+        Ok(Self(value.to_vec()))
     }
 }
 
-impl<T> From<FieldArray<T, ENTROPY_BYTES>> for Entropy
+impl<T, const N: usize> From<FieldArray<T, N>> for Entropy
 where
+    T: Zero + Clone,
     u8: From<T>,
 {
-    fn from(value: FieldArray<T, ENTROPY_BYTES>) -> Self {
-        let bytes = value.into_iter().map(u8::from).collect::<Vec<_>>();
-        bytes_to_bits(&bytes).as_slice().try_into().unwrap()
+    fn from(value: FieldArray<T, N>) -> Self {
+        let bytes = Zeroizing::new(value.iter().cloned().map(u8::from).collect::<Vec<_>>());
+        let bits = Zeroizing::new(bytes_to_bits(&bytes));
+        bits.as_slice()
+            .try_into()
+            .expect("Field array length should match a valid entropy length")
     }
 }
 
-impl<T> From<&Entropy> for FieldArray<T, ENTROPY_BYTES>
+impl<T, const N: usize> From<&Entropy> for FieldArray<T, N>
 where
-    T: From<u8> + Debug,
+    T: From<u8> + Zero,
 {
     fn from(value: &Entropy) -> Self {
-        value.to_bytes().map(T::from).into()
+        let bytes = value.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            N,
+            "Entropy length should match the field array length"
+        );
+        array::from_fn(|i| T::from(bytes[i])).into()
     }
 }
 
-/// The checksum of a bip-39 secret.
+/// The checksum of a bip-39 secret (one bit for every 32 bits of entropy). The bits are wiped
+/// when the checksum is dropped.
 #[derive(PartialEq, Eq)]
 #[cfg_attr(test, derive(Clone, Debug))]
-struct Checksum([bool; CHECKSUM_BITS]);
+struct Checksum(Vec<bool>);
+
+impl Drop for Checksum {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
 
 impl TryFrom<&[bool]> for Checksum {
-    type Error = TryFromSliceError;
+    type Error = Error;
 
-    fn try_from(value: &[bool]) -> Result<Self, Self::Error> {
-        Ok(Self(value.try_into()?))
+    fn try_from(value: &[bool]) -> Result<Self> {
+        let length = value.len();
+        if !MNEMONIC_WORDS.iter().any(|&w| checksum_bits(w) == length) {
+            return Err(Error::InvalidChecksumLength { actual: length });
+        }
+        Ok(Self(value.to_vec()))
     }
 }
 
 impl From<&Entropy> for Checksum {
     fn from(entropy: &Entropy) -> Self {
-        let digest = Sha256::digest(entropy.to_bytes());
-        let bits = bytes_to_bits(digest.as_ref());
-        let checksum = bits[..CHECKSUM_BITS]
-            .try_into()
-            .expect("Slice size should match the checksum bit length");
-        Self(checksum)
+        let digest = Sha256::digest(entropy.to_bytes().as_slice());
+        let bits = Zeroizing::new(bytes_to_bits(digest.as_ref()));
+        Self(bits[..checksum_bits(entropy.mnemonic_words())].to_vec())
     }
 }
 
@@ -158,90 +557,601 @@ pub struct Bip39Secret {
 }
 
 impl ShamirSecretSharing for Bip39Secret {
-    fn split<R: CryptoRng + RngCore>(&self, n: u8, t: u8, rng: &mut R) -> Vec<Bip39Share> {
-        FieldArray::<gf256, ENTROPY_BYTES>::from(&self.entropy)
-            .split(n, t, rng)
-            .into_iter()
-            .map(|share| {
-                let (id, secret) = share.into_inner();
-                let entropy = Entropy::from(secret);
-                Bip39Share::new(id, Self::from(entropy))
-            })
-            .collect()
+    fn split<R: CryptoRng + RngCore>(&self, n: u8, t: u8, rng: &mut R) -> Result<Vec<Bip39Share>> {
+        match self.entropy.as_bits().len() / 8 {
+            16 => self.split_array::<16, R>(n, t, rng),
+            20 => self.split_array::<20, R>(n, t, rng),
+            24 => self.split_array::<24, R>(n, t, rng),
+            28 => self.split_array::<28, R>(n, t, rng),
+            32 => self.split_array::<32, R>(n, t, rng),
+            _ => unreachable!("Entropy length should be valid by construction"),
+        }
     }
 
-    fn reconstruct<S: AsRef<Bip39Share>>(shares: &[S]) -> Self {
-        let array_shares = shares
-            .iter()
-            .map(|share| {
-                let (id, secret) = share.as_ref().as_coordinates();
-                let array = FieldArray::from(&secret.entropy);
-                ShamirShare::new(*id, array)
-            })
-            .collect::<Vec<_>>();
-
-        let array = FieldArray::<gf256, ENTROPY_BYTES>::reconstruct(&array_shares);
-        let entropy = Entropy::from(array);
-        Self::from(entropy)
+    /// Reconstruct a secret from `t` shares. Fail if no shares are provided, if the share IDs are
+    /// not distinct and non-zero, or if the shares encode mnemonics of different lengths.
+    fn reconstruct<S: AsRef<Bip39Share>>(shares: &[S]) -> Result<Self> {
+        match Self::shares_entropy_bytes(shares)? {
+            16 => Self::reconstruct_array::<16, S>(shares),
+            20 => Self::reconstruct_array::<20, S>(shares),
+            24 => Self::reconstruct_array::<24, S>(shares),
+            28 => Self::reconstruct_array::<28, S>(shares),
+            32 => Self::reconstruct_array::<32, S>(shares),
+            _ => unreachable!("Entropy length should be valid by construction"),
+        }
     }
 }
 
 impl Bip39Secret {
+    /// Split the secret like [`ShamirSecretSharing::split`], and also commit to the polynomial the
+    /// shares lie on. Return the shares, the public commitment, and the (private) proof of each
+    /// share in the same order, for verifiable secret sharing.
+    pub fn split_verifiable<R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<(Vec<Bip39Share>, PolynomialCommitment, Vec<ShareProof>)> {
+        match self.entropy.as_bits().len() / 8 {
+            16 => self.split_array_verifiable::<16, R>(n, t, rng),
+            20 => self.split_array_verifiable::<20, R>(n, t, rng),
+            24 => self.split_array_verifiable::<24, R>(n, t, rng),
+            28 => self.split_array_verifiable::<28, R>(n, t, rng),
+            32 => self.split_array_verifiable::<32, R>(n, t, rng),
+            _ => unreachable!("Entropy length should be valid by construction"),
+        }
+    }
+
     /// Ensure the checksum of the secret is valid.
     pub fn is_valid(&self) -> Result<()> {
         let checksum = Checksum::from(&self.entropy);
-        ensure!(self.checksum == checksum, "Invalid checksum");
+        if self.checksum != checksum {
+            return Err(Error::InvalidChecksum);
+        }
         Ok(())
     }
 
-    /// Create a new secret from a given mnemonic.
+    /// The number of words of the mnemonic encoding the secret.
+    pub fn mnemonic_words(&self) -> usize {
+        self.entropy.mnemonic_words()
+    }
+
+    /// The entropy of the secret as bytes (the checksum is derived from it).
+    pub(crate) fn entropy_bytes(&self) -> Zeroizing<Vec<u8>> {
+        self.entropy.to_bytes()
+    }
+
+    /// Lock the memory holding the secret in RAM so that it is never written to swap.
+    pub fn lock_memory(&self) -> io::Result<()> {
+        memory::lock(self.entropy.as_bits())?;
+        memory::lock(self.checksum.as_bits())
+    }
+
+    /// Create a new secret from a given mnemonic of 12, 15, 18, 21, or 24 words. The mnemonic
+    /// is NFKD-normalized before being parsed.
     pub fn from_mnemonic(mnemonic: &str, dictionary: &Bip39Dictionary) -> Result<Self> {
+        let mnemonic = normalize(mnemonic);
         let words = mnemonic.split_whitespace().collect::<Vec<_>>();
         let length = words.len();
+        if !MNEMONIC_WORDS.contains(&length) {
+            return Err(Error::InvalidMnemonicLength {
+                expected: &MNEMONIC_WORDS,
+                actual: length,
+            });
+        }
 
-        let bits = TryInto::<[&str; MNEMONIC_WORDS]>::try_into(words)
-            .map_err(|_| eyre!("Invalid mnemonic length {length} != {MNEMONIC_WORDS}"))?
-            .into_iter()
-            .map(|word| dictionary.bits_from_word(word))
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+        let mut bits = Zeroizing::new(Vec::with_capacity(length * DICTIONARY_INDICES_BITS));
+        for (i, word) in words.into_iter().enumerate() {
+            let mut word_bits = dictionary.bits_from_word(word).map_err(|e| e.at_word(i))?;
+            bits.extend(word_bits);
+            word_bits.zeroize();
+        }
 
+        let entropy_bits = entropy_bits(length);
         Ok(Self {
-            entropy: bits[..ENTROPY_BITS]
+            entropy: bits[..entropy_bits]
                 .try_into()
                 .expect("Valid mnemonic should be longer than the entropy bit length"),
-            checksum: bits[ENTROPY_BITS..].try_into().expect(
+            checksum: bits[entropy_bits..].try_into().expect(
                 "Valid mnemonic should match the sum of the entropy and checksum bit length",
             ),
         })
     }
 
     /// Generate a mnemonic from the secret.
+    /// The mnemonic is written into a buffer of its exact length, so that growing it leaves no
+    /// unwiped copy of its words on the heap.
     pub fn to_mnemonic(&self, dictionary: &Bip39Dictionary) -> String {
-        self.entropy
-            .as_bits()
-            .iter()
-            .cloned()
-            .chain(self.checksum.as_bits().iter().cloned())
-            .collect::<Vec<_>>()
-            .chunks(DICTIONARY_INDICES_BITS)
-            .map(|chunk| {
+        let bits = Zeroizing::new(
+            self.entropy
+                .as_bits()
+                .iter()
+                .cloned()
+                .chain(self.checksum.as_bits().iter().cloned())
+                .collect::<Vec<_>>(),
+        );
+        let words = || {
+            bits.chunks(DICTIONARY_INDICES_BITS).map(|chunk| {
                 let bits = chunk.try_into().expect(
                     "The secret bit length should be divisible by the dictionary index bit length",
                 );
                 dictionary.word_from_bits(bits)
             })
-            .collect::<Vec<_>>()
-            .join(" ")
+        };
+
+        let separator = dictionary.separator();
+        let length = words()
+            .map(|word| word.len() + separator.len())
+            .sum::<usize>();
+        let mut mnemonic = String::with_capacity(length);
+        for (i, word) in words().enumerate() {
+            if i > 0 {
+                mnemonic.push_str(separator);
+            }
+            mnemonic.push_str(word);
+        }
+        mnemonic
+    }
+
+    /// Correct the misspelled words of a mnemonic by trying all combinations of their suggested
+    /// replacements, and return the corrected mnemonics with a valid checksum.
+    pub fn corrections(mnemonic: &str, dictionary: &Bip39Dictionary) -> Result<Vec<String>> {
+        let mnemonic = normalize(mnemonic);
+        let length = mnemonic.split_whitespace().count();
+        if !MNEMONIC_WORDS.contains(&length) {
+            return Err(Error::InvalidMnemonicLength {
+                expected: &MNEMONIC_WORDS,
+                actual: length,
+            });
+        }
+
+        let candidates = mnemonic
+            .split_whitespace()
+            .map(|word| match dictionary.find(word) {
+                Ok(Some(index)) => Zeroizing::new(vec![index]),
+                _ => Zeroizing::new(
+                    dictionary
+                        .suggestions(word)
+                        .into_iter()
+                        .map(|suggestion| dictionary.indices[suggestion])
+                        .collect(),
+                ),
+            })
+            .collect::<Vec<_>>();
+
+        let misspelled = mnemonic
+            .split_whitespace()
+            .filter(|w| !dictionary.contains(w))
+            .count();
+        if misspelled > MAX_CORRECTED_WORDS {
+            return Err(Error::TooManyMisspelledWords {
+                max: MAX_CORRECTED_WORDS,
+                actual: misspelled,
+            });
+        }
+
+        let mut mnemonics = vec![Zeroizing::new(Vec::new())];
+        for indices in candidates {
+            mnemonics = mnemonics
+                .into_iter()
+                .flat_map(|prefix| {
+                    // Reserve the full length so that the indices are never reallocated (leaving
+                    // unwiped copies).
+                    indices.iter().map(move |index| {
+                        let mut mnemonic = Zeroizing::new(Vec::with_capacity(length));
+                        mnemonic.extend_from_slice(&prefix);
+                        mnemonic.push(*index);
+                        mnemonic
+                    })
+                })
+                .collect();
+        }
+
+        // Test the checksum of the candidates before writing them out, so that the rejected
+        // candidates (which are close to the secret) are only ever held as wiped indices.
+        Ok(mnemonics
+            .into_iter()
+            .map(|indices| Self::from_indices(&indices))
+            .filter(|secret| secret.is_valid().is_ok())
+            .map(|secret| secret.to_mnemonic(dictionary))
+            .collect())
+    }
+
+    /// Recover up to two missing words of a mnemonic by trying all dictionary words in their
+    /// place, and return the candidate secrets with a valid checksum. Missing words at known
+    /// positions are marked with `?`; missing words at unknown positions are simply left out, in
+    /// which case they are tried at every position.
+    pub fn recover_words(mnemonic: &str, dictionary: &Bip39Dictionary) -> Result<Vec<Self>> {
+        let mut secrets = Vec::new();
+        Self::search_missing_words(
+            mnemonic,
+            dictionary,
+            MAX_RECOVERY_CANDIDATES,
+            |secret| {
+                secrets.push(secret);
+                Ok(())
+            },
+            |_, _| (),
+        )?;
+        Ok(secrets)
+    }
+
+    /// Search the missing words of a mnemonic like [`Self::recover_words`], but pass each
+    /// candidate secret with a valid checksum to `candidate` as soon as it is found (each
+    /// candidate once, even if several placements of the missing words produce it) rather than
+    /// collecting them. `progress` is regularly called with the number of candidate mnemonics
+    /// tried so far and their total number. Fail if more than `max_candidates` mnemonics would be
+    /// tried, or as soon as `candidate` fails.
+    pub fn search_missing_words<C, P>(
+        mnemonic: &str,
+        dictionary: &Bip39Dictionary,
+        max_candidates: usize,
+        mut candidate: C,
+        mut progress: P,
+    ) -> Result<()>
+    where
+        C: FnMut(Self) -> Result<()>,
+        P: FnMut(usize, usize),
+    {
+        let mnemonic = normalize(mnemonic);
+        let provided = Zeroizing::new(
+            mnemonic
+                .split_whitespace()
+                .enumerate()
+                .map(|(i, word)| match word {
+                    MISSING_WORD => Ok(None),
+                    _ => dictionary
+                        .index_of(word)
+                        .map(Some)
+                        .map_err(|e| e.at_word(i)),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        );
+
+        // Find the shortest mnemonic length fitting the provided words.
+        let length = provided.len();
+        let words = MNEMONIC_WORDS
+            .into_iter()
+            .find(|words| *words >= length)
+            .ok_or(Error::InvalidMnemonicLength {
+                expected: &MNEMONIC_WORDS,
+                actual: length,
+            })?;
+        let unknown_positions = words - length;
+        let missing = provided.iter().filter(|i| i.is_none()).count() + unknown_positions;
+        if missing == 0 {
+            return Err(Error::NoMissingWords);
+        }
+        if missing > MAX_MISSING_WORDS {
+            return Err(Error::TooManyMissingWords {
+                max: MAX_MISSING_WORDS,
+                actual: missing,
+            });
+        }
+
+        // Place the missing words at every possible position.
+        let mut patterns = vec![provided.to_vec()];
+        for _ in 0..unknown_positions {
+            patterns = patterns
+                .into_iter()
+                .flat_map(|pattern| {
+                    (0..=pattern.len()).map(move |i| {
+                        let mut pattern = pattern.clone();
+                        pattern.insert(i, None);
+                        pattern
+                    })
+                })
+                .collect();
+            patterns.sort();
+            patterns.dedup();
+        }
+
+        let total = patterns
+            .len()
+            .saturating_mul(DICTIONARY_WORDS.saturating_pow(missing as u32));
+        if total > max_candidates {
+            return Err(Error::TooManyCandidates {
+                max: max_candidates,
+                actual: total,
+            });
+        }
+
+        let mut entropy = Zeroizing::new(vec![0; entropy_bits(words) / 8]);
+        let mut tried = 0;
+        for pattern in patterns.into_iter().map(Zeroizing::new) {
+            let slots = (0..pattern.len())
+                .filter(|i| pattern[*i].is_none())
+                .collect::<Vec<_>>();
+            let mut indices = Zeroizing::new(
+                pattern
+                    .iter()
+                    .map(|index| index.unwrap_or(0))
+                    .collect::<Vec<_>>(),
+            );
+
+            // Enumerate all combinations of dictionary words in the slots (as an odometer). A
+            // mnemonic produced by several placements of the missing words is only reported for
+            // the placement putting the provided words at their earliest positions.
+            loop {
+                if Self::checksum_matches(&indices, &mut entropy)
+                    && *Zeroizing::new(Self::earliest_placement(&provided, &indices)) == *pattern
+                {
+                    candidate(Self::from_indices(&indices))?;
+                }
+                tried += 1;
+                if tried % RECOVERY_PROGRESS_INTERVAL == 0 {
+                    progress(tried, total);
+                }
+
+                let Some(slot) = slots
+                    .iter()
+                    .find(|slot| (indices[**slot] as usize) + 1 < DICTIONARY_WORDS)
+                else {
+                    break;
+                };
+                for previous in slots.iter().take_while(|previous| *previous != slot) {
+                    indices[*previous] = 0;
+                }
+                indices[*slot] += 1;
+            }
+        }
+        progress(total, total);
+        Ok(())
+    }
+
+    /// Check whether the dictionary indices of the words of a mnemonic have a valid checksum,
+    /// without building the secret. The entropy is written to the scratch buffer `entropy` (of the
+    /// entropy length in bytes).
+    fn checksum_matches(indices: &[u16], entropy: &mut [u8]) -> bool {
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        let mut length = 0;
+        for index in indices {
+            buffer = (buffer << DICTIONARY_INDICES_BITS) | *index as u32;
+            bits += DICTIONARY_INDICES_BITS;
+            while bits >= 8 && length < entropy.len() {
+                bits -= 8;
+                entropy[length] = (buffer >> bits) as u8;
+                length += 1;
+            }
+            buffer &= (1 << bits) - 1;
+        }
+
+        // The remaining bits are the checksum: the first bits of the digest of the entropy.
+        let digest = Sha256::digest(&*entropy);
+        let matches = buffer == (digest.as_ref()[0] >> (8 - bits)) as u32;
+        buffer.zeroize();
+        matches
+    }
+
+    /// Place the provided words of a mnemonic (`None` for a `?`) at their earliest positions in
+    /// a candidate mnemonic, each `?` taking the position right after the previous word. Return
+    /// the placement as the provided words at their positions and `None` elsewhere.
+    fn earliest_placement(provided: &[Option<u16>], indices: &[u16]) -> Vec<Option<u16>> {
+        let mut placement = vec![None; indices.len()];
+        let mut position = 0;
+        for word in provided {
+            if let Some(index) = word {
+                while indices[position] != *index {
+                    position += 1;
+                }
+                placement[position] = Some(*index);
+            }
+            position += 1;
+        }
+        placement
+    }
+
+    /// Create a new secret from the dictionary indices of the words of a mnemonic. The number of
+    /// indices must be a valid mnemonic length.
+    fn from_indices(indices: &[u16]) -> Self {
+        let bits = Zeroizing::new(
+            indices
+                .iter()
+                .flat_map(|index| {
+                    (0..DICTIONARY_INDICES_BITS)
+                        .rev()
+                        .map(move |i| (index >> i) & 1 == 1)
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let entropy_bits = entropy_bits(indices.len());
+        Self {
+            entropy: Entropy(bits[..entropy_bits].to_vec()),
+            checksum: Checksum(bits[entropy_bits..].to_vec()),
+        }
+    }
+
+    /// Generate a mnemonic from the secret where each word is shortened to its stem (its first
+    /// four letters), as typically stamped on metal backups. Fail if the words of the dictionary
+    /// are not identified by their stems, or if a stem does not identify a single word (without
+    /// revealing which, since the error may be printed).
+    pub fn to_stems(&self, dictionary: &Bip39Dictionary) -> Result<String> {
+        if !dictionary.has_unique_stems() {
+            return Err(Error::AmbiguousStems);
+        }
+        let mnemonic = Zeroizing::new(self.to_mnemonic(dictionary));
+        let separator = dictionary.separator();
+        let mut stems = Zeroizing::new(String::with_capacity(mnemonic.len()));
+        for (i, word) in mnemonic.split(separator).enumerate() {
+            if i > 0 {
+                stems.push_str(separator);
+            }
+            stems.push_str(Bip39Dictionary::stem(word));
+        }
+        match Self::from_mnemonic(&stems, dictionary) {
+            Ok(secret) if secret == *self => Ok(mem::take(&mut stems)),
+            _ => Err(Error::AmbiguousStems),
+        }
+    }
+
+    /// Split the secret using a field array of `N` bytes (the entropy length).
+    fn split_array<const N: usize, R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<Vec<Bip39Share>> {
+        let shares = FieldArray::<Gf256, N>::from(&self.entropy).split(n, t, rng)?;
+        Ok(Self::from_array_shares(shares))
+    }
+
+    /// Split the secret using a field array of `N` bytes (the entropy length), and commit to the
+    /// polynomial the shares lie on.
+    fn split_array_verifiable<const N: usize, R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<(Vec<Bip39Share>, PolynomialCommitment, Vec<ShareProof>)> {
+        let (shares, polynomial) =
+            FieldArray::<Gf256, N>::from(&self.entropy).split_with_polynomial(n, t, rng)?;
+        let coefficients = polynomial
+            .coefficients()
+            .iter()
+            .map(|coefficient| Zeroizing::new(coefficient.iter().map(|x| u8::from(*x)).collect()))
+            .collect::<Vec<_>>();
+        let (commitment, proofs) = commitment::commit(&coefficients, n, rng);
+        Ok((Self::from_array_shares(shares), commitment, proofs))
+    }
+
+    /// Convert shares of field arrays of `N` bytes into bip-39 shares.
+    fn from_array_shares<const N: usize>(
+        shares: Vec<ShamirShare<FieldArray<Gf256, N>>>,
+    ) -> Vec<Bip39Share> {
+        shares
+            .into_iter()
+            .map(|share| {
+                let (id, secret) = share.into_inner();
+                let entropy = Entropy::from(secret);
+                Bip39Share::new(id, Self::from(entropy))
+            })
+            .collect()
+    }
+
+    /// Reconstruct a secret split with threshold `t` from `n` shares of which up to `(n - t) / 2`
+    /// may be corrupted (e.g., mistyped or mixed up with another secret's), and return the
+    /// (sorted) IDs of the shares disagreeing with the secret. Fail if fewer than `t` shares are
+    /// provided, if the shares encode mnemonics of different lengths, if the share IDs are not
+    /// distinct and non-zero, or if too many shares are corrupted. The checksums of the shares
+    /// are not checked, since corrupted shares may have invalid ones.
+    pub fn reconstruct_with_errors<S: AsRef<Bip39Share>>(
+        shares: &[S],
+        t: u8,
+    ) -> Result<(Self, Vec<u8>)> {
+        match Self::shares_entropy_bytes(shares)? {
+            16 => Self::reconstruct_array_with_errors::<16, S>(shares, t),
+            20 => Self::reconstruct_array_with_errors::<20, S>(shares, t),
+            24 => Self::reconstruct_array_with_errors::<24, S>(shares, t),
+            28 => Self::reconstruct_array_with_errors::<28, S>(shares, t),
+            32 => Self::reconstruct_array_with_errors::<32, S>(shares, t),
+            _ => unreachable!("Entropy length should be valid by construction"),
+        }
+    }
+
+    /// The entropy length (in bytes) of a set of shares. Fail if no shares are provided or if
+    /// the shares encode mnemonics of different lengths.
+    fn shares_entropy_bytes<S: AsRef<Bip39Share>>(shares: &[S]) -> Result<usize> {
+        let first = shares.first().ok_or(Error::NoShares)?;
+        let words = first.as_ref().secret().mnemonic_words();
+        if shares
+            .iter()
+            .any(|share| share.as_ref().secret().mnemonic_words() != words)
+        {
+            return Err(Error::MismatchedShareLengths);
+        }
+        Ok(first.as_ref().secret().entropy.as_bits().len() / 8)
+    }
+
+    /// Convert shares into shares of field arrays of `N` bytes (the entropy length).
+    fn array_shares<const N: usize, S: AsRef<Bip39Share>>(
+        shares: &[S],
+    ) -> Vec<ShamirShare<FieldArray<Gf256, N>>> {
+        shares
+            .iter()
+            .map(|share| {
+                let (id, secret) = share.as_ref().as_coordinates();
+                let array = FieldArray::from(&secret.entropy);
+                ShamirShare::new(*id, array)
+            })
+            .collect()
+    }
+
+    /// Reconstruct the secret using field arrays of `N` bytes (the entropy length).
+    fn reconstruct_array<const N: usize, S: AsRef<Bip39Share>>(shares: &[S]) -> Result<Self> {
+        let array = FieldArray::<Gf256, N>::reconstruct(&Self::array_shares::<N, S>(shares))?;
+        let entropy = Entropy::from(array);
+        Ok(Self::from(entropy))
+    }
+
+    /// Reconstruct the secret while correcting corrupted shares, using field arrays of `N` bytes
+    /// (the entropy length).
+    fn reconstruct_array_with_errors<const N: usize, S: AsRef<Bip39Share>>(
+        shares: &[S],
+        t: u8,
+    ) -> Result<(Self, Vec<u8>)> {
+        let array_shares = Self::array_shares::<N, S>(shares);
+        let (array, corrupted) = FieldArray::<Gf256, N>::reconstruct_with_errors(&array_shares, t)?;
+        let entropy = Entropy::from(array);
+        Ok((Self::from(entropy), corrupted))
+    }
+
+    /// Generate a new secret of `mnemonic_words` words. The entropy is drawn from `rng`, which
+    /// should be seeded by the OS (e.g., `OsRng`) when creating a wallet.
+    pub fn generate<R: CryptoRng + RngCore>(mnemonic_words: usize, rng: &mut R) -> Result<Self> {
+        if !MNEMONIC_WORDS.contains(&mnemonic_words) {
+            return Err(Error::InvalidMnemonicLength {
+                expected: &MNEMONIC_WORDS,
+                actual: mnemonic_words,
+            });
+        }
+        Ok(Self::from(Entropy::random(mnemonic_words, rng)))
+    }
+
+    /// Create a new secret of `mnemonic_words` words from random bits collected externally (e.g.,
+    /// from dice rolls). Fail if there are not enough bits; the extra bits are ignored.
+    pub fn from_entropy_bits(bits: &[bool], mnemonic_words: usize) -> Result<Self> {
+        if !MNEMONIC_WORDS.contains(&mnemonic_words) {
+            return Err(Error::InvalidMnemonicLength {
+                expected: &MNEMONIC_WORDS,
+                actual: mnemonic_words,
+            });
+        }
+        let needed = entropy_bits(mnemonic_words);
+        if bits.len() < needed {
+            return Err(Error::NotEnoughEntropy {
+                expected: needed,
+                actual: bits.len(),
+            });
+        }
+        Ok(Self::from(Entropy::try_from(&bits[..needed])?))
+    }
+
+    /// Mix randomness drawn from `rng` into the secret by XOR-ing it with the entropy (and
+    /// recomputing the checksum). The result is unpredictable as long as either source is.
+    pub fn mix<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Self {
+        let random = Entropy::random(self.mnemonic_words(), rng);
+        let bits = self
+            .entropy
+            .as_bits()
+            .iter()
+            .zip(random.as_bits())
+            .map(|(a, b)| a ^ b)
+            .collect();
+        Self::from(Entropy(bits))
+    }
+
+    #[cfg(test)]
+    pub fn random_with_words<R: CryptoRng + RngCore>(mnemonic_words: usize, rng: &mut R) -> Self {
+        Self::from(Entropy::random(mnemonic_words, rng))
     }
 }
 
 #[cfg(test)]
 impl crate::shamir::Random for Bip39Secret {
     fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
-        Self::from(Entropy::random(rng))
+        Self::random_with_words(24, rng)
     }
 }
 
@@ -267,22 +1177,141 @@ impl Bip39Share {
     pub fn to_mnemonic(&self, dictionary: &Bip39Dictionary) -> String {
         self.secret().to_mnemonic(dictionary)
     }
+
+    pub fn to_stems(&self, dictionary: &Bip39Dictionary) -> Result<String> {
+        self.secret().to_stems(dictionary)
+    }
+
+    /// Check whether the share lies on the same polynomial as other shares of a secret split
+    /// with threshold `t`. Fail if fewer than `t` other shares are provided.
+    pub fn is_consistent(&self, others: &[Bip39Share], t: u8) -> Result<bool> {
+        let t = t as usize;
+        if t == 0 {
+            return Err(Error::ZeroThreshold);
+        }
+        if others.len() < t {
+            return Err(Error::NotEnoughShares {
+                expected: t,
+                actual: others.len(),
+            });
+        }
+        let words = self.secret().mnemonic_words();
+        if others.iter().any(|s| s.secret().mnemonic_words() != words) {
+            return Err(Error::MismatchedShareLengths);
+        }
+        let mut all = others.iter().collect::<Vec<_>>();
+        all.push(self);
+        check_shares(&all)?;
+
+        // Two polynomials of degree `t - 1` agreeing on `t - 1` shares share the same secret only
+        // if they are equal.
+        let expected = Bip39Secret::reconstruct(&others[..t])?;
+        let mut shares = others[..t - 1].iter().collect::<Vec<_>>();
+        shares.push(self);
+        Ok(Bip39Secret::reconstruct(&shares)? == expected)
+    }
+}
+
+/// Re-encode a mnemonic (of a secret or a share) with the words of another dictionary. The
+/// entropy and checksum are preserved, so a converted share keeps its ID and can be combined with
+/// shares encoded in any other language. Fail if the mnemonic is not valid.
+pub fn convert_mnemonic(
+    mnemonic: &str,
+    from: &Bip39Dictionary,
+    to: &Bip39Dictionary,
+) -> Result<String> {
+    let secret = Bip39Secret::from_mnemonic(mnemonic, from)?;
+    secret.is_valid()?;
+    Ok(secret.to_mnemonic(to))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        fs::read_to_string,
+        slice,
+        str,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
 
     use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use zeroize::Zeroizing;
 
     use crate::{
-        bip39::{Bip39Dictionary, Bip39Secret, Bip39Share, ENTROPY_BITS},
+        bip39::{
+            convert_mnemonic,
+            entropy_bits,
+            Bip39Dictionary,
+            Bip39Secret,
+            Bip39Share,
+            Language,
+            MAX_RECOVERY_CANDIDATES,
+            MNEMONIC_WORDS,
+        },
+        error::Error,
         shamir::{self, Random, ShamirSecretSharing},
     };
 
+    /// The addresses of the heap buffers to inspect when they are freed (zero if unused).
+    static WATCHED: [AtomicUsize; 3] = [
+        AtomicUsize::new(0),
+        AtomicUsize::new(0),
+        AtomicUsize::new(0),
+    ];
+    /// The number of watched buffers that were (or were not) wiped when freed.
+    static WIPED: AtomicUsize = AtomicUsize::new(0);
+    static NOT_WIPED: AtomicUsize = AtomicUsize::new(0);
+
+    /// The stem of a word of the test mnemonic, and the number of buffers freed (by a thread
+    /// searching them) while still holding it.
+    const WATCHED_WORD: &[u8] = b"libe";
+    static LEAKED_WORDS: AtomicUsize = AtomicUsize::new(0);
+
+    thread_local! {
+        /// Whether the buffers freed by the current thread are searched for the watched word.
+        static SEARCHING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// An allocator checking whether the watched buffers are wiped when they are freed.
+    struct WipeCheckingAllocator;
+
+    unsafe impl GlobalAlloc for WipeCheckingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            for watched in &WATCHED {
+                let address = ptr as usize;
+                if watched
+                    .compare_exchange(address, 0, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+                {
+                    let bytes = slice::from_raw_parts(ptr, layout.size());
+                    match bytes.iter().all(|b| *b == 0) {
+                        true => WIPED.fetch_add(1, Ordering::SeqCst),
+                        false => NOT_WIPED.fetch_add(1, Ordering::SeqCst),
+                    };
+                }
+            }
+            if SEARCHING.try_with(Cell::get).unwrap_or(false) {
+                let bytes = slice::from_raw_parts(ptr, layout.size());
+                if bytes.windows(WATCHED_WORD.len()).any(|w| w == WATCHED_WORD) {
+                    LEAKED_WORDS.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: WipeCheckingAllocator = WipeCheckingAllocator;
+
     /// Load the default bip-39 dictionary.
     fn test_dictionary() -> Bip39Dictionary {
-        Bip39Dictionary::load("assets/bip39-en.txt").unwrap()
+        Bip39Dictionary::builtin(Language::English).unwrap()
     }
 
     /// A valid bip-39 mnemonic.
@@ -295,10 +1324,116 @@ mod tests {
 
     #[test]
     fn load_dictionary() {
-        let dictionary = test_dictionary();
+        let dictionary = Bip39Dictionary::load("assets/bip39-en.txt").unwrap();
         assert_eq!(dictionary.words.len(), 2048);
     }
 
+    #[test]
+    fn english_dictionary() {
+        let dictionary = test_dictionary();
+        let loaded = Bip39Dictionary::load("assets/bip39-en.txt").unwrap();
+        assert_eq!(dictionary.words, loaded.words);
+    }
+
+    #[test]
+    fn builtin_dictionaries() {
+        for language in Language::ALL {
+            let dictionary = Bip39Dictionary::builtin(language).unwrap();
+            assert_eq!(dictionary.words.len(), 2048);
+        }
+    }
+
+    #[test]
+    fn detect_language() {
+        let japanese = "あいこくしん\u{3000}あいこくしん\u{3000}あおぞら";
+        assert_eq!(
+            Language::detect([test_mnemonic()]).unwrap(),
+            Language::English
+        );
+        assert_eq!(Language::detect([japanese]).unwrap(), Language::Japanese);
+
+        // Some words are both in the English and French wordlists.
+        assert!(Language::detect(["abandon animal badge"]).is_err());
+        assert_eq!(
+            Language::detect(["abandon animal badge", test_mnemonic()]).unwrap(),
+            Language::English
+        );
+
+        // All mnemonics must use the same wordlist.
+        assert!(matches!(
+            Language::detect([test_mnemonic(), japanese]),
+            Err(Error::MixedLanguages)
+        ));
+        assert!(Language::detect(["abandon notaword"]).is_err());
+    }
+
+    #[test]
+    fn dictionary_digest() {
+        let dictionary = test_dictionary();
+        assert_eq!(
+            dictionary.digest(),
+            "2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda"
+        );
+        assert!(dictionary.warnings().is_empty());
+    }
+
+    #[test]
+    fn invalid_dictionaries() {
+        let words = test_dictionary().words.to_vec();
+        let parse = |words: &[String]| Bip39Dictionary::parse(&words.join("\n"), " ");
+        assert!(parse(&words).is_ok());
+
+        // Too few words.
+        assert!(matches!(
+            parse(&words[1..]),
+            Err(Error::InvalidDictionaryLength {
+                expected: 2048,
+                actual: 2047
+            })
+        ));
+
+        // Duplicate words.
+        let mut duplicates = words.clone();
+        duplicates[1] = duplicates[0].clone();
+        assert!(matches!(
+            parse(&duplicates),
+            Err(Error::DuplicateDictionaryWord {
+                first_line: 1,
+                line: 2,
+                ..
+            })
+        ));
+
+        // Empty words.
+        let mut empty = words.clone();
+        empty[1] = String::new();
+        assert!(matches!(
+            parse(&empty),
+            Err(Error::EmptyDictionaryWord { line: 2 })
+        ));
+
+        // Words with whitespace.
+        let mut whitespace = words.clone();
+        whitespace[1].push_str(" x");
+        assert!(matches!(
+            parse(&whitespace),
+            Err(Error::DictionaryWordWithWhitespace { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn dictionary_warnings() {
+        let mut words = test_dictionary().words.to_vec();
+        words.swap(0, 1);
+        words[2] = "abandonment".to_string();
+        let dictionary = Bip39Dictionary::parse(&words.join("\n"), " ").unwrap();
+
+        let warnings = dictionary.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("abandon, abandonment"));
+        assert!(warnings[1].contains("not sorted"));
+    }
+
     #[test]
     fn bits_from_word() {
         let dictionary = test_dictionary();
@@ -313,6 +1448,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bits_from_prefix() {
+        let dictionary = test_dictionary();
+        let expected = dictionary.bits_from_word("abandon").unwrap();
+
+        assert_eq!(dictionary.bits_from_word("aban").unwrap(), expected);
+        assert_eq!(dictionary.bits_from_word("abando").unwrap(), expected);
+        assert!(dictionary.bits_from_word("aba").is_err());
+        assert!(dictionary.bits_from_word("abandons").is_err());
+    }
+
+    #[test]
+    fn bits_from_ambiguous_prefix() {
+        // The first four letters do not identify all words of the Korean wordlist.
+        let dictionary = Bip39Dictionary::builtin(Language::Korean).unwrap();
+        let stem = dictionary
+            .words
+            .iter()
+            .map(|word| Bip39Dictionary::stem(word))
+            .find(|stem| {
+                dictionary
+                    .words
+                    .iter()
+                    .filter(|w| w.starts_with(stem))
+                    .count()
+                    > 1
+            })
+            .unwrap();
+
+        let error = dictionary.bits_from_word(stem).unwrap_err();
+        assert!(error.to_string().contains("Ambiguous"));
+    }
+
+    #[test]
+    fn suggestions() {
+        let dictionary = test_dictionary();
+
+        // Substituting a neighbouring key ranks before other edits.
+        assert_eq!(dictionary.suggestions("abamdon"), vec!["abandon"]);
+        assert_eq!(dictionary.suggestions("hols")[0], "hold");
+        assert_eq!(dictionary.suggestions("xylophone"), Vec::<&str>::new());
+
+        let error = dictionary.bits_from_word("abamdon").unwrap_err();
+        assert!(error.to_string().contains("did you mean: abandon?"));
+    }
+
+    #[test]
+    fn word_indices() {
+        let dictionary = test_dictionary();
+        for (i, word) in dictionary.words.iter().enumerate() {
+            let bits = dictionary.bits_from_word(word).unwrap();
+            assert_eq!(Bip39Dictionary::index_from_bits(&bits), i as u16);
+            assert_eq!(dictionary.word(i as u16), word);
+        }
+    }
+
     #[test]
     fn word_from_bits() {
         let dictionary = test_dictionary();
@@ -349,12 +1540,353 @@ mod tests {
             .flat_map(|word| dictionary.bits_from_word(word).unwrap())
             .collect::<Vec<_>>();
 
-        assert_eq!(secret.entropy, expected[..ENTROPY_BITS].try_into().unwrap());
+        let entropy_bits = entropy_bits(24);
+        assert_eq!(secret.entropy, expected[..entropy_bits].try_into().unwrap());
         assert_eq!(
             secret.checksum,
-            expected[ENTROPY_BITS..].try_into().unwrap()
+            expected[entropy_bits..].try_into().unwrap()
+        );
+        assert!(secret.is_valid().is_ok());
+    }
+
+    #[test]
+    fn from_mnemonic_short() {
+        let dictionary = test_dictionary();
+        let mnemonic =
+            "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+        let secret = Bip39Secret::from_mnemonic(mnemonic, &dictionary).unwrap();
+        assert_eq!(secret.mnemonic_words(), 12);
+        assert!(secret.is_valid().is_ok());
+        assert_eq!(secret.to_mnemonic(&dictionary), mnemonic);
+    }
+
+    #[test]
+    fn from_mnemonic_japanese() {
+        let dictionary = Bip39Dictionary::builtin(Language::Japanese).unwrap();
+        let mnemonic =
+            "あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}\
+            あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}\
+            あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あおぞら";
+
+        let secret = Bip39Secret::from_mnemonic(mnemonic, &dictionary).unwrap();
+        assert!(secret.is_valid().is_ok());
+
+        let exported = secret.to_mnemonic(&dictionary);
+        assert_eq!(exported.split('\u{3000}').count(), 12);
+        assert_eq!(
+            secret,
+            Bip39Secret::from_mnemonic(&exported, &dictionary).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_mnemonic_nfkd() {
+        let dictionary = Bip39Dictionary::builtin(Language::Spanish).unwrap();
+        let composed = "\u{e1}baco \u{e1}lbum l\u{ed}quido \u{e1}lbum espuma acudir \
+            bolero bosque amante gaita dictar boca";
+        let decomposed = "a\u{301}baco a\u{301}lbum li\u{301}quido a\u{301}lbum espuma acudir \
+            bolero bosque amante gaita dictar boca";
+
+        let secret = Bip39Secret::from_mnemonic(composed, &dictionary).unwrap();
+        assert!(secret.is_valid().is_ok());
+        assert_eq!(
+            secret,
+            Bip39Secret::from_mnemonic(decomposed, &dictionary).unwrap()
+        );
+        assert_eq!(secret.to_mnemonic(&dictionary), decomposed);
+    }
+
+    #[test]
+    fn to_stems() {
+        let dictionary = test_dictionary();
+        let secret = Bip39Secret::from_mnemonic(test_mnemonic(), &dictionary).unwrap();
+
+        let stems = secret.to_stems(&dictionary).unwrap();
+        assert!(stems.starts_with("moti doma empl libe prio mora boil prop urge"));
+        assert_eq!(
+            secret,
+            Bip39Secret::from_mnemonic(&stems, &dictionary).unwrap()
+        );
+    }
+
+    #[test]
+    fn to_stems_not_unique() {
+        // Decomposed Hangul letters make the stems of the Korean words ambiguous: the error must
+        // not reveal (part of) the mnemonic.
+        let dictionary = Bip39Dictionary::builtin(Language::Korean).unwrap();
+        assert!(!dictionary.has_unique_stems());
+        assert!(test_dictionary().has_unique_stems());
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let secret = Bip39Secret::generate(24, &mut rng).unwrap();
+            assert!(matches!(
+                secret.to_stems(&dictionary),
+                Err(Error::AmbiguousStems)
+            ));
+        }
+    }
+
+    #[test]
+    fn from_mnemonic_invalid_word() {
+        let dictionary = test_dictionary();
+        let mnemonic = test_mnemonic().replace("liberty", "libertu");
+
+        let error = Bip39Secret::from_mnemonic(&mnemonic, &dictionary).unwrap_err();
+        match &error {
+            Error::InvalidWord {
+                index,
+                word,
+                suggestions,
+            } => {
+                assert_eq!(*index, Some(3));
+                assert_eq!(word, "libertu");
+                assert!(suggestions.contains(&"liberty".to_string()));
+            }
+            _ => panic!("Unexpected error {error:?}"),
+        }
+        assert!(error
+            .to_string()
+            .starts_with("Invalid BIP-39 word #4 'libertu'"));
+    }
+
+    #[test]
+    fn corrections() {
+        let dictionary = test_dictionary();
+        let mnemonic = test_mnemonic()
+            .replace("liberty", "libertu")
+            .replace("venue", "venie");
+
+        let corrections = Bip39Secret::corrections(&mnemonic, &dictionary).unwrap();
+        assert!(corrections.contains(&test_mnemonic().to_string()));
+        for correction in corrections {
+            let secret = Bip39Secret::from_mnemonic(&correction, &dictionary).unwrap();
+            assert!(secret.is_valid().is_ok());
+        }
+    }
+
+    #[test]
+    fn recover_words() {
+        let dictionary = test_dictionary();
+        let secret = Bip39Secret::from_mnemonic(test_mnemonic(), &dictionary).unwrap();
+
+        // A missing word at a known position.
+        let mnemonic = test_mnemonic().replace("liberty", "?");
+        let candidates = Bip39Secret::recover_words(&mnemonic, &dictionary).unwrap();
+        assert!(candidates.contains(&secret));
+        assert!(candidates.iter().all(|c| c.is_valid().is_ok()));
+
+        // A missing word at an unknown position.
+        let mnemonic = test_mnemonic().replace("liberty ", "");
+        let candidates = Bip39Secret::recover_words(&mnemonic, &dictionary).unwrap();
+        assert!(candidates.contains(&secret));
+
+        // Each candidate is listed once, even if the missing word can be placed next to an
+        // identical word.
+        for i in 0..candidates.len() {
+            assert!(!candidates[i + 1..].contains(&candidates[i]));
+        }
+
+        // Too many missing words.
+        let mnemonic = test_mnemonic()
+            .replacen("bullet", "?", 2)
+            .replace("liberty", "?");
+        assert!(Bip39Secret::recover_words(&mnemonic, &dictionary).is_err());
+    }
+
+    #[test]
+    fn recover_words_candidates() {
+        let dictionary = test_dictionary();
+        let words = test_mnemonic().split_whitespace().collect::<Vec<_>>();
+        let search = |mnemonic: &str, max_candidates| {
+            let mut reported = 0;
+            let result = Bip39Secret::search_missing_words(
+                mnemonic,
+                &dictionary,
+                max_candidates,
+                |_| Ok(()),
+                |tried, total| reported = tried.max(total),
+            );
+            (result, reported)
+        };
+
+        // Two missing words at unknown positions of a 24-word mnemonic are tried at each of the
+        // 276 pairs of positions, and are within the default limit.
+        let mnemonic = words[..22].join(" ");
+        let expected = 276 * 2048 * 2048;
+        assert!(expected <= MAX_RECOVERY_CANDIDATES);
+        assert!(matches!(
+            search(&mnemonic, 0),
+            (Err(Error::TooManyCandidates { max: 0, actual }), 0) if actual == expected
+        ));
+
+        // One missing word at a known position and one at an unknown position.
+        let mnemonic = format!("? {}", words[..22].join(" "));
+        assert!(matches!(
+            search(&mnemonic, 1 << 20),
+            (Err(Error::TooManyCandidates { actual, .. }), 0) if actual == 23 * 2048 * 2048
+        ));
+
+        // The progress reaches the total number of candidates.
+        let mnemonic = words[1..].join(" ");
+        let (result, reported) = search(&mnemonic, MAX_RECOVERY_CANDIDATES);
+        assert!(result.is_ok());
+        assert_eq!(reported, 24 * 2048);
+    }
+
+    /// Run with `cargo test --release -- --ignored recover_words_unknown_positions`.
+    #[test]
+    #[ignore = "slow"]
+    fn recover_words_unknown_positions() {
+        let dictionary = test_dictionary();
+        let mnemonic =
+            "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let secret = Bip39Secret::from_mnemonic(mnemonic, &dictionary).unwrap();
+
+        // Two missing words at unknown positions of a 12-word mnemonic.
+        let partial = mnemonic.replace("sausage ", "").replacen("thank ", "", 1);
+        let mut found = 0;
+        Bip39Secret::search_missing_words(
+            &partial,
+            &dictionary,
+            MAX_RECOVERY_CANDIDATES,
+            |candidate| {
+                assert!(candidate.is_valid().is_ok());
+                if candidate == secret {
+                    found += 1;
+                }
+                Ok(())
+            },
+            |_, _| (),
+        )
+        .unwrap();
+        assert_eq!(found, 1);
+    }
+
+    #[test]
+    fn consistent_shares() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let secret = Bip39Secret::random(&mut rng);
+        let mut shares = secret.split(4, 2, &mut rng).unwrap();
+        let share = shares.remove(0);
+
+        assert!(share.is_consistent(&shares, 2).unwrap());
+        assert!(share.is_consistent(&shares[..1], 2).is_err());
+
+        let other = Bip39Secret::random(&mut rng)
+            .split(1, 1, &mut rng)
+            .unwrap()
+            .remove(0);
+        assert!(!other.is_consistent(&shares, 2).unwrap());
+        assert!(!other.is_consistent(&shares, 1).unwrap());
+    }
+
+    #[test]
+    fn generate() {
+        let mut rng = StdRng::from_seed([0; 32]);
+        for words in MNEMONIC_WORDS {
+            let secret = Bip39Secret::generate(words, &mut rng).unwrap();
+            assert!(secret.is_valid().is_ok());
+            assert_eq!(secret.mnemonic_words(), words);
+        }
+        assert!(Bip39Secret::generate(13, &mut rng).is_err());
+    }
+
+    #[test]
+    fn from_entropy_bits() {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let secret = Bip39Secret::random_with_words(12, &mut rng);
+
+        // Extra bits are ignored.
+        let mut bits = secret.entropy.as_bits().to_vec();
+        bits.extend([true; 10]);
+        assert_eq!(Bip39Secret::from_entropy_bits(&bits, 12).unwrap(), secret);
+
+        // Not enough bits.
+        assert!(matches!(
+            Bip39Secret::from_entropy_bits(&bits, 15),
+            Err(Error::NotEnoughEntropy {
+                expected: 160,
+                actual: 138
+            })
+        ));
+        assert!(Bip39Secret::from_entropy_bits(&bits, 13).is_err());
+
+        // Mixing changes the entropy but keeps a valid checksum.
+        let mixed = secret.mix(&mut rng);
+        assert!(mixed.is_valid().is_ok());
+        assert_eq!(mixed.mnemonic_words(), 12);
+        assert_ne!(mixed, secret);
+    }
+
+    #[test]
+    fn from_mnemonic_invalid_length() {
+        let dictionary = test_dictionary();
+        let words = test_mnemonic().split_whitespace().collect::<Vec<_>>();
+
+        for length in [0, 1, 11, 13, 23, 25] {
+            let mnemonic = words.iter().cycle().take(length).cloned();
+            let mnemonic = mnemonic.collect::<Vec<_>>().join(" ");
+            assert!(Bip39Secret::from_mnemonic(&mnemonic, &dictionary).is_err());
+        }
+    }
+
+    #[test]
+    fn wiped_on_drop() {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let secret = Bip39Secret::random_with_words(24, &mut rng);
+        assert!(secret.entropy.as_bits().iter().any(|b| *b));
+        assert!(secret.checksum.as_bits().iter().any(|b| *b));
+
+        // A plain vector is not wiped (which ensures the allocator detects unwiped buffers).
+        let plain = secret.entropy.as_bits().to_vec();
+        WATCHED[0].store(plain.as_ptr() as usize, Ordering::SeqCst);
+        WATCHED[1].store(secret.entropy.as_bits().as_ptr() as usize, Ordering::SeqCst);
+        WATCHED[2].store(
+            secret.checksum.as_bits().as_ptr() as usize,
+            Ordering::SeqCst,
         );
+        drop(plain);
+        drop(secret);
+
+        assert_eq!(NOT_WIPED.load(Ordering::SeqCst), 1);
+        assert_eq!(WIPED.load(Ordering::SeqCst), 2);
+
+        // The words of a mnemonic are not copied to the heap without being wiped.
+        let dictionary = test_dictionary();
+        let mnemonic = Zeroizing::new(test_mnemonic().to_string());
+        let misspelled = Zeroizing::new(test_mnemonic().replace("venue", "venie"));
+        assert!(mnemonic.contains(str::from_utf8(WATCHED_WORD).unwrap()));
+
+        // A plain string is not wiped (which ensures the allocator detects unwiped copies).
+        SEARCHING.with(|searching| searching.set(true));
+        drop(String::from_utf8(WATCHED_WORD.to_vec()).unwrap());
+        SEARCHING.with(|searching| searching.set(false));
+        assert_eq!(LEAKED_WORDS.load(Ordering::SeqCst), 1);
+
+        // Parsing, checking, and correcting the mnemonic leaves no copy of its words.
+        SEARCHING.with(|searching| searching.set(true));
+        for word in mnemonic.split_whitespace() {
+            assert!(dictionary.contains(word));
+        }
+        let secret = Bip39Secret::from_mnemonic(&mnemonic, &dictionary).unwrap();
         assert!(secret.is_valid().is_ok());
+        drop(secret);
+        let corrections = Bip39Secret::corrections(&misspelled, &dictionary).unwrap();
+        assert!(corrections.contains(&mnemonic));
+        drop(Zeroizing::new(corrections));
+
+        // Printing the words or stems of a secret or a share leaves no copy of them either.
+        let secret = Bip39Secret::from_mnemonic(&mnemonic, &dictionary).unwrap();
+        assert_eq!(*Zeroizing::new(secret.to_mnemonic(&dictionary)), *mnemonic);
+        drop(Zeroizing::new(secret.to_stems(&dictionary).unwrap()));
+        let share = Bip39Share::from_mnemonic(1, &mnemonic, &dictionary).unwrap();
+        drop(Zeroizing::new(share.to_mnemonic(&dictionary)));
+        drop(Zeroizing::new(share.to_stems(&dictionary).unwrap()));
+        drop((secret, share));
+        SEARCHING.with(|searching| searching.set(false));
+        assert_eq!(LEAKED_WORDS.load(Ordering::SeqCst), 1);
     }
 
     #[test]
@@ -371,26 +1903,91 @@ mod tests {
         let dictionary = test_dictionary();
 
         let mut rng = StdRng::seed_from_u64(0);
-        let secret = Bip39Secret::random(&mut rng);
+        for words in MNEMONIC_WORDS {
+            let secret = Bip39Secret::random_with_words(words, &mut rng);
+
+            let n = 5;
+            let t = 3;
+            let shares = secret.split(n, t, &mut rng).unwrap();
+
+            assert_eq!(shares.len(), n as usize);
+            for i in 0..t {
+                let share = &shares[i as usize];
+                let id = i + 1;
+
+                assert_eq!(share.id(), &id);
+                assert!(share.is_valid().is_ok());
+
+                let share_mnemonic = share.to_mnemonic(&dictionary);
+                assert_eq!(share_mnemonic.split_whitespace().count(), words);
+                assert_eq!(
+                    share,
+                    &Bip39Share::from_mnemonic(id, &share_mnemonic, &dictionary).unwrap()
+                );
+            }
+        }
+    }
 
-        let n = 5;
-        let t = 3;
-        let shares = secret.split(n, t, &mut rng);
+    #[test]
+    fn reconstruct_mixed_lengths() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let short = Bip39Secret::random_with_words(12, &mut rng).split(3, 2, &mut rng);
+        let long = Bip39Secret::random_with_words(24, &mut rng).split(3, 2, &mut rng);
+        let (short, long) = (short.unwrap(), long.unwrap());
+        assert!(matches!(
+            Bip39Secret::reconstruct(&[&short[0], &long[1]]),
+            Err(Error::MismatchedShareLengths)
+        ));
+    }
+
+    #[test]
+    fn reconstruct_invalid_shares() {
+        shamir::test::test_reconstruct_invalid_shares::<Bip39Secret>();
+    }
 
-        assert_eq!(shares.len(), n as usize);
-        for i in 0..t {
-            let share = &shares[i as usize];
-            let id = i + 1;
+    #[test]
+    fn split_invalid_parameters() {
+        shamir::test::test_split_invalid_parameters::<Bip39Secret>();
+    }
 
-            assert_eq!(share.id(), &id);
-            assert!(share.is_valid().is_ok());
+    #[test]
+    fn convert() {
+        let english = test_dictionary();
+        let japanese = Bip39Dictionary::builtin(Language::Japanese).unwrap();
+        let mnemonic = test_mnemonic();
 
-            let share_mnemonic = share.to_mnemonic(&dictionary);
-            assert_eq!(
-                share,
-                &Bip39Share::from_mnemonic(id, &share_mnemonic, &dictionary).unwrap()
-            );
-        }
+        let converted = convert_mnemonic(mnemonic, &english, &japanese).unwrap();
+        assert_eq!(
+            Language::detect([converted.as_str()]).unwrap(),
+            Language::Japanese
+        );
+        assert_eq!(
+            convert_mnemonic(&converted, &japanese, &english).unwrap(),
+            mnemonic
+        );
+    }
+
+    #[test]
+    fn convert_shares() {
+        let english = test_dictionary();
+        let mut rng = StdRng::seed_from_u64(0);
+        let secret = Bip39Secret::random(&mut rng);
+        let shares = secret.split(3, 2, &mut rng).unwrap();
+
+        // Convert each share to a different language and reconstruct from the converted shares.
+        let converted = shares
+            .iter()
+            .zip([Language::French, Language::Korean, Language::Czech])
+            .map(|(share, language)| {
+                let dictionary = Bip39Dictionary::builtin(language).unwrap();
+                let mnemonic = share.to_mnemonic(&english);
+                let converted = convert_mnemonic(&mnemonic, &english, &dictionary).unwrap();
+                Bip39Share::from_mnemonic(*share.id(), &converted, &dictionary).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(converted, shares);
+        assert_eq!(Bip39Secret::reconstruct(&converted[1..]).unwrap(), secret);
     }
 
     #[test]
@@ -419,11 +2016,11 @@ mod tests {
         let dictionary = test_dictionary();
 
         let mut rng = StdRng::seed_from_u64(0);
-        for n in 1..=15 {
+        for (n, words) in (1..=15).zip(MNEMONIC_WORDS.iter().cycle()) {
             for t in 1..=n {
-                let secret = Bip39Secret::random(&mut rng);
+                let secret = Bip39Secret::random_with_words(*words, &mut rng);
 
-                let mut shares = secret.clone().split(n, t, &mut rng);
+                let mut shares = secret.clone().split(n, t, &mut rng).unwrap();
                 shares.shuffle(&mut rng);
 
                 for share in &shares {
@@ -435,7 +2032,7 @@ mod tests {
                 }
 
                 for i in 1..=t {
-                    let reconstructed = Bip39Secret::reconstruct(&shares[0..i as usize]);
+                    let reconstructed = Bip39Secret::reconstruct(&shares[0..i as usize]).unwrap();
                     assert!(reconstructed.is_valid().is_ok());
 
                     if i == t {
@@ -0,0 +1,746 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifiable secret sharing: public commitments to the polynomial a secret is split with, and a
+//! proof for each share that it lies on the committed polynomial, so that each shareholder can
+//! check their share (and that it is consistent with the others) without the others.
+//!
+//! Feldman and Pedersen commitments need shares that are evaluations of the polynomial in the
+//! scalar field of a prime-order group, but shares are evaluated in GF(256). Multiplying by a
+//! constant of GF(256) is linear over GF(2) though: each bit of a share is the XOR of a set of bits
+//! of the coefficients that only depends on the share's ID. So the dealer publishes a Pedersen
+//! commitment `b·G + r·H` over ristretto255 to every bit `b` of every coefficient, with proofs that
+//! each commits to 0 or 1. A share bit `y` is then the XOR of the committed bits of a set `S` if
+//! and only if the sum of their commitments minus `y·G` commits to an even number (at most `|S|`),
+//! which the proof of the share shows for all its bits with OR-proofs.
+//!
+//! The commitments are perfectly hiding: they reveal nothing about the secret or the shares, and
+//! can be published. The proof of a share is private, like the blinding factors of Pedersen's
+//! scheme: it only reveals that the share is consistent, but its challenge is bound to the whole
+//! share, so anyone knowing most of the share could test guesses of the rest against it. Keep it
+//! with the share.
+
+use std::{
+    fmt,
+    ptr,
+    str::FromStr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use fastcrypto::{
+    groups::{
+        ristretto255::{RistrettoPoint, RistrettoScalar},
+        GroupElement,
+        MultiScalarMul,
+    },
+    hash::{HashFunction, Sha512},
+    serde_helpers::ToFromByteArray,
+};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use crate::{
+    bip39::Bip39Share,
+    error::{Error, Result},
+    gf256::Gf256,
+};
+
+/// The domain separator of the generator of the blinding factors.
+const GENERATOR_DOMAIN: &[u8] = b"shamir-bip39/vss-generator/v1";
+/// The domain separator of the challenges of the proofs.
+const CHALLENGE_DOMAIN: &[u8] = b"shamir-bip39/vss-challenge/v1";
+/// The first word of a formatted polynomial commitment.
+const COMMITMENT_HEADER: &str = "shamir-bip39-commitment-v1";
+/// The first word of a formatted share proof.
+const PROOF_HEADER: &str = "shamir-bip39-proof-v1";
+/// The entropy lengths (in bytes) of the supported mnemonics.
+const ENTROPY_LENGTHS: [usize; 5] = [16, 20, 24, 28, 32];
+
+/// Public commitments to the polynomial a secret is split with, formatted as a header line
+/// "shamir-bip39-commitment-v1 THRESHOLD LENGTH" followed by one hex-encoded element per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolynomialCommitment {
+    /// The threshold of the split (the number of coefficients of the polynomial).
+    threshold: u8,
+    /// The length of the secret (and of each coefficient) in bytes.
+    length: usize,
+    /// The commitment to bit `k` of byte `b` of coefficient `j`, at index
+    /// `(j * length + b) * 8 + k`.
+    bits: Vec<RistrettoPoint>,
+    /// The proof that each commitment is to 0 or 1.
+    proof: OrProof,
+}
+
+impl PolynomialCommitment {
+    /// The threshold of the split (the number of shares needed to reconstruct the secret).
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Check that every commitment is to a bit, i.e., that the commitments are to a polynomial of
+    /// degree less than the threshold.
+    pub fn verify(&self) -> Result<()> {
+        let statements = bit_statements(&self.bits);
+        if !self.proof.verify(&self.context(), &statements) {
+            return Err(Error::InvalidCommitmentProof);
+        }
+        Ok(())
+    }
+
+    /// Check that a share lies on the committed polynomial using its proof. Fail if the proof is
+    /// for another ID, if the share has another length than the secret, if the commitments are
+    /// not to bits, or if the share does not match its proof.
+    pub fn verify_share(&self, share: &Bip39Share, proof: &ShareProof) -> Result<()> {
+        let id = *share.id();
+        if id != proof.id {
+            return Err(Error::ShareIdMismatch {
+                share: id,
+                proof: proof.id,
+            });
+        }
+        let entropy = share.secret().entropy_bytes();
+        if entropy.len() != self.length {
+            return Err(Error::MismatchedShareLengths);
+        }
+        self.verify()?;
+
+        let statements = self.share_statements(id, &entropy);
+        if !proof.proof.verify(&self.share_context(id), &statements.0) {
+            return Err(Error::CommitmentMismatch { id });
+        }
+        Ok(())
+    }
+
+    /// The context of the proof that the commitments are to bits.
+    fn context(&self) -> Vec<u8> {
+        format!("{COMMITMENT_HEADER} {} {}", self.threshold, self.length).into_bytes()
+    }
+
+    /// The context of the proof of the share with a given ID, binding it to the commitments.
+    fn share_context(&self, id: u8) -> Vec<u8> {
+        let mut hash = Sha512::default();
+        hash.update(self.context());
+        for point in &self.bits {
+            hash.update(point.to_byte_array());
+        }
+        let mut context = hash.finalize().digest.to_vec();
+        context.extend_from_slice(format!("{PROOF_HEADER} {id}").as_bytes());
+        context
+    }
+
+    /// The statements proven for the share with a given ID and entropy: for bit `l` of byte `b`
+    /// of the share, one of the points `A - y·G - 2v·G` (for `v` from 0 to `|S| / 2`) is a
+    /// multiple of H, where `A` is the sum of the commitments to the bits `S` whose XOR is the
+    /// share bit `y`.
+    fn share_statements(&self, id: u8, entropy: &[u8]) -> SecretPoints {
+        let generator = RistrettoPoint::generator();
+        let sets = share_bit_sets(id, self.threshold as usize);
+
+        let mut statements = Vec::with_capacity(self.length * 8);
+        for (b, byte) in entropy.iter().enumerate() {
+            for (l, set) in sets.iter().enumerate() {
+                let mut point = set.iter().fold(RistrettoPoint::zero(), |sum, (j, k)| {
+                    sum + self.bits[(j * self.length + b) * 8 + k]
+                });
+                if byte >> l & 1 == 1 {
+                    point -= generator;
+                }
+                let mut branches = Vec::with_capacity(set.len() / 2 + 1);
+                for _ in 0..=set.len() / 2 {
+                    branches.push(point);
+                    point -= generator + generator;
+                }
+                statements.push(branches);
+            }
+        }
+        SecretPoints(statements)
+    }
+}
+
+impl fmt::Display for PolynomialCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{COMMITMENT_HEADER} {} {}", self.threshold, self.length)?;
+        for point in &self.bits {
+            write_hex(f, &point.to_byte_array())?;
+        }
+        write!(f, "{}", self.proof)
+    }
+}
+
+impl FromStr for PolynomialCommitment {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut tokens = s.split_whitespace();
+        if tokens.next() != Some(COMMITMENT_HEADER) {
+            return Err(Error::InvalidCommitment);
+        }
+        let mut parameter = || {
+            tokens
+                .next()
+                .and_then(|token| token.parse::<u8>().ok())
+                .ok_or(Error::InvalidCommitment)
+        };
+        let (threshold, length) = (parameter()?, parameter()? as usize);
+        if threshold == 0 || !ENTROPY_LENGTHS.contains(&length) {
+            return Err(Error::InvalidCommitment);
+        }
+
+        let count = threshold as usize * length * 8;
+        let elements = tokens.map(parse_hex).collect::<Option<Vec<_>>>();
+        let elements = elements.ok_or(Error::InvalidCommitment)?;
+        if elements.len() != count + 1 + 4 * count {
+            return Err(Error::InvalidCommitment);
+        }
+        let bits = elements[..count]
+            .iter()
+            .map(|bytes| RistrettoPoint::from_byte_array(bytes).ok())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::InvalidCommitment)?;
+        let proof = OrProof::from_elements(&elements[count..]).ok_or(Error::InvalidCommitment)?;
+        Ok(Self {
+            threshold,
+            length,
+            bits,
+            proof,
+        })
+    }
+}
+
+/// The proof that a share lies on the committed polynomial, formatted as a header line
+/// "shamir-bip39-proof-v1 ID" followed by one hex-encoded element per line. The proof can be used
+/// to test guesses of the share, so it must be kept as private as the share itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareProof {
+    /// The ID of the share.
+    id: u8,
+    /// The proof that each bit of the share is the XOR of the committed coefficient bits.
+    proof: OrProof,
+}
+
+impl ShareProof {
+    /// Get the ID of the share.
+    pub fn id(&self) -> &u8 {
+        &self.id
+    }
+}
+
+impl fmt::Display for ShareProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{PROOF_HEADER} {}", self.id)?;
+        write!(f, "{}", self.proof)
+    }
+}
+
+impl FromStr for ShareProof {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut tokens = s.split_whitespace();
+        if tokens.next() != Some(PROOF_HEADER) {
+            return Err(Error::InvalidShareProof);
+        }
+        let id = tokens
+            .next()
+            .and_then(|token| token.parse::<u8>().ok())
+            .filter(|id| *id != 0)
+            .ok_or(Error::InvalidShareProof)?;
+        let elements = tokens.map(parse_hex).collect::<Option<Vec<_>>>();
+        let proof = elements
+            .and_then(|elements| OrProof::from_elements(&elements))
+            .ok_or(Error::InvalidShareProof)?;
+        Ok(Self { id, proof })
+    }
+}
+
+/// Commit to a polynomial given by the bytes of its coefficients (starting with the secret), and
+/// prove that each of the shares with IDs 1 to `n` lies on it.
+pub(crate) fn commit<R: CryptoRng + RngCore>(
+    coefficients: &[Zeroizing<Vec<u8>>],
+    n: u8,
+    rng: &mut R,
+) -> (PolynomialCommitment, Vec<ShareProof>) {
+    let threshold = coefficients.len();
+    let length = coefficients[0].len();
+    let (generator, blinding_generator) = (RistrettoPoint::generator(), blinding_generator());
+
+    // Commit to each bit of each coefficient.
+    let mut bits = Zeroizing::new(Vec::with_capacity(threshold * length * 8));
+    let mut blindings = Zeroizing::new(Vec::with_capacity(threshold * length * 8));
+    for byte in coefficients
+        .iter()
+        .flat_map(|coefficient| coefficient.iter())
+    {
+        for k in 0..8 {
+            bits.push((byte >> k & 1) as usize);
+            blindings.push(random_scalar(rng));
+        }
+    }
+    let points = bits
+        .iter()
+        .zip(blindings.iter())
+        .map(|(bit, blinding)| {
+            generator * RistrettoScalar::from(*bit as u64) + blinding_generator * blinding
+        })
+        .collect::<Vec<_>>();
+
+    // Prove that each commitment is to a bit (the first branch for 0, the second for 1).
+    let context = format!("{COMMITMENT_HEADER} {threshold} {length}").into_bytes();
+    let proof = OrProof::prove(&context, &bit_statements(&points), &bits, &blindings, rng);
+    let commitment = PolynomialCommitment {
+        threshold: threshold as u8,
+        length,
+        bits: points,
+        proof,
+    };
+
+    // Prove that each bit of each share is the XOR of the committed bits of its set: the sum of
+    // their commitments minus the share bit is a commitment to twice the number of pairs of ones.
+    let proofs = (1..=n)
+        .map(|id| {
+            let sets = share_bit_sets(id, threshold);
+            let mut entropy = Zeroizing::new(vec![0u8; length]);
+            let mut branches = Zeroizing::new(Vec::with_capacity(length * 8));
+            let mut witnesses = Zeroizing::new(Vec::with_capacity(length * 8));
+            for (b, byte) in entropy.iter_mut().enumerate() {
+                for (l, set) in sets.iter().enumerate() {
+                    let (mut ones, mut witness) = (0, RistrettoScalar::zero());
+                    for (j, k) in set {
+                        let index = (j * length + b) * 8 + k;
+                        ones += bits[index];
+                        witness += blindings[index];
+                    }
+                    *byte |= ((ones & 1) as u8) << l;
+                    branches.push(ones / 2);
+                    witnesses.push(witness);
+                }
+            }
+
+            let statements = commitment.share_statements(id, &entropy);
+            let context = commitment.share_context(id);
+            let proof = OrProof::prove(&context, &statements.0, &branches, &witnesses, rng);
+            ShareProof { id, proof }
+        })
+        .collect();
+    (commitment, proofs)
+}
+
+/// Check a share against the commitment to the polynomial and the proof with the share's ID.
+/// Fail if no proof has the share's ID or if the share does not lie on the committed polynomial.
+pub fn verify_share(
+    share: &Bip39Share,
+    commitment: &PolynomialCommitment,
+    proofs: &[ShareProof],
+) -> Result<()> {
+    let proof = proofs
+        .iter()
+        .find(|proof| proof.id() == share.id())
+        .ok_or(Error::MissingShareProof { id: *share.id() })?;
+    commitment.verify_share(share, proof)
+}
+
+/// Non-interactive OR-proofs (Cramer, Damgård, and Schoenmakers, with the Fiat-Shamir heuristic)
+/// that, in each of several statements, one of the points is a multiple of H (a commitment to 0).
+/// All the statements share a single challenge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct OrProof {
+    /// The challenge, split among the branches of each statement.
+    challenge: RistrettoScalar,
+    /// The challenge and the response of each branch of each statement.
+    responses: Vec<(RistrettoScalar, RistrettoScalar)>,
+}
+
+impl OrProof {
+    /// Prove the statements given, for each of them, the index of a branch that is a multiple of
+    /// H and its discrete logarithm.
+    fn prove<R: CryptoRng + RngCore>(
+        context: &[u8],
+        statements: &[Vec<RistrettoPoint>],
+        branches: &[usize],
+        witnesses: &[RistrettoScalar],
+        rng: &mut R,
+    ) -> Self {
+        let blinding_generator = blinding_generator();
+
+        // Commit to a nonce for the true branch and simulate the other branches.
+        let mut nonces = Zeroizing::new(Vec::with_capacity(statements.len()));
+        let mut announcements = Vec::new();
+        let mut responses = Vec::new();
+        for (points, branch) in statements.iter().zip(branches) {
+            let nonce = random_scalar(rng);
+            for (i, point) in points.iter().enumerate() {
+                if i == *branch {
+                    announcements.push(blinding_generator * nonce);
+                    responses.push((RistrettoScalar::zero(), RistrettoScalar::zero()));
+                } else {
+                    let (challenge, response) = (random_scalar(rng), random_scalar(rng));
+                    announcements.push(blinding_generator * response - *point * challenge);
+                    responses.push((challenge, response));
+                }
+            }
+            nonces.push(nonce);
+        }
+
+        // The true branch gets the rest of the challenge.
+        let challenge = challenge(context, statements, &announcements);
+        let mut offset = 0;
+        for (((points, branch), witness), nonce) in statements
+            .iter()
+            .zip(branches)
+            .zip(witnesses)
+            .zip(nonces.iter())
+        {
+            let responses = &mut responses[offset..offset + points.len()];
+            let simulated = responses
+                .iter()
+                .fold(RistrettoScalar::zero(), |sum, (challenge, _)| {
+                    sum + challenge
+                });
+            let remaining = challenge - simulated;
+            responses[*branch] = (remaining, *nonce + remaining * *witness);
+            offset += points.len();
+        }
+        Self {
+            challenge,
+            responses,
+        }
+    }
+
+    /// Check the proof of the statements.
+    fn verify(&self, context: &[u8], statements: &[Vec<RistrettoPoint>]) -> bool {
+        let count = statements.iter().map(Vec::len).sum::<usize>();
+        if self.responses.len() != count {
+            return false;
+        }
+        let blinding_generator = blinding_generator();
+
+        let mut announcements = Vec::with_capacity(count);
+        let mut responses = self.responses.iter();
+        for points in statements {
+            let mut sum = RistrettoScalar::zero();
+            for (point, (challenge, response)) in points.iter().zip(responses.by_ref()) {
+                let announcement = RistrettoPoint::multi_scalar_mul(
+                    &[*response, -*challenge],
+                    &[blinding_generator, *point],
+                )
+                .expect("There should be as many scalars as points");
+                announcements.push(announcement);
+                sum += challenge;
+            }
+            if sum != self.challenge {
+                return false;
+            }
+        }
+        challenge(context, statements, &announcements) == self.challenge
+    }
+
+    /// Decode a proof from its challenge followed by the challenge and response of each branch.
+    fn from_elements(elements: &[[u8; 32]]) -> Option<Self> {
+        let (challenge, responses) = elements.split_first()?;
+        if responses.len() % 2 != 0 {
+            return None;
+        }
+        let scalars = responses
+            .iter()
+            .map(|bytes| RistrettoScalar::from_byte_array(bytes).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            challenge: RistrettoScalar::from_byte_array(challenge).ok()?,
+            responses: scalars.chunks(2).map(|pair| (pair[0], pair[1])).collect(),
+        })
+    }
+}
+
+impl fmt::Display for OrProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.challenge.to_byte_array())?;
+        for (challenge, response) in &self.responses {
+            write_hex(f, &challenge.to_byte_array())?;
+            write_hex(f, &response.to_byte_array())?;
+        }
+        Ok(())
+    }
+}
+
+/// Points derived from a share, which reveal it given the commitments. They are wiped when
+/// dropped.
+struct SecretPoints(Vec<Vec<RistrettoPoint>>);
+
+impl Drop for SecretPoints {
+    fn drop(&mut self) {
+        for point in self.0.iter_mut().flatten() {
+            // SAFETY: The pointer is derived from a valid mutable reference.
+            unsafe { ptr::write_volatile(point, RistrettoPoint::zero()) };
+        }
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
+/// The generator H of the blinding factors, whose discrete logarithm to the base G is unknown.
+fn blinding_generator() -> RistrettoPoint {
+    RistrettoPoint::hash_to_ristretto255_with_dst(&[b"H"], GENERATOR_DOMAIN)
+}
+
+/// The statements that each commitment `C` is to a bit: either `C` or `C - G` is a multiple of H.
+fn bit_statements(bits: &[RistrettoPoint]) -> Vec<Vec<RistrettoPoint>> {
+    let generator = RistrettoPoint::generator();
+    bits.iter()
+        .map(|point| vec![*point, *point - generator])
+        .collect()
+}
+
+/// The coefficient bits whose XOR is each bit of the share with a given ID: bit `l` of byte `b`
+/// of the share is the XOR of bit `k` of byte `b` of coefficient `j` for all `(j, k)` in the `l`-th
+/// set, since the share is the sum of the coefficients multiplied by powers of the ID.
+fn share_bit_sets(id: u8, threshold: usize) -> [Vec<(usize, usize)>; 8] {
+    let mut sets: [Vec<(usize, usize)>; 8] = Default::default();
+    let mut power = Gf256::from(1);
+    for j in 0..threshold {
+        for k in 0..8 {
+            let column = u8::from(power * Gf256::from(1 << k));
+            for (l, set) in sets.iter_mut().enumerate() {
+                if column >> l & 1 == 1 {
+                    set.push((j, k));
+                }
+            }
+        }
+        power *= Gf256::from(id);
+    }
+    sets
+}
+
+/// The Fiat-Shamir challenge of statements given the announcements of their branches.
+fn challenge(
+    context: &[u8],
+    statements: &[Vec<RistrettoPoint>],
+    announcements: &[RistrettoPoint],
+) -> RistrettoScalar {
+    let mut hash = Sha512::default();
+    hash.update(CHALLENGE_DOMAIN);
+    hash.update(context);
+    for point in statements.iter().flatten().chain(announcements) {
+        hash.update(point.to_byte_array());
+    }
+    RistrettoScalar::from_bytes_mod_order_wide(&hash.finalize().digest)
+}
+
+/// Draw a scalar uniformly at random (from 64 random bytes, to make the modular bias negligible).
+fn random_scalar<R: CryptoRng + RngCore>(rng: &mut R) -> RistrettoScalar {
+    let mut bytes = Zeroizing::new([0; 64]);
+    rng.fill_bytes(bytes.as_mut());
+    RistrettoScalar::from_bytes_mod_order_wide(&bytes)
+}
+
+/// Write an element as a line of hex.
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8; 32]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    writeln!(f)
+}
+
+/// Parse an element written as hex.
+fn parse_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use fastcrypto::groups::{ristretto255::RistrettoPoint, GroupElement};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{verify_share, PolynomialCommitment, ShareProof};
+    use crate::{
+        bip39::{convert_mnemonic, Bip39Dictionary, Bip39Secret, Bip39Share, Language},
+        error::Error,
+    };
+
+    fn test_split(t: u8) -> (Vec<Bip39Share>, PolynomialCommitment, Vec<ShareProof>) {
+        let mut rng = StdRng::seed_from_u64(t as u64);
+        let secret = Bip39Secret::generate(12, &mut rng).unwrap();
+        secret.split_verifiable(4, t, &mut rng).unwrap()
+    }
+
+    #[test]
+    fn verify() {
+        for t in 1..=3 {
+            let (shares, commitment, proofs) = test_split(t);
+            assert_eq!(commitment.threshold(), t);
+            commitment.verify().unwrap();
+            for share in &shares {
+                verify_share(share, &commitment, &proofs).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn verify_mismatch() {
+        let (shares, commitment, proofs) = test_split(2);
+
+        // A share does not match the proof of another share.
+        assert!(matches!(
+            commitment.verify_share(&shares[1], &proofs[0]),
+            Err(Error::ShareIdMismatch { share: 2, proof: 1 })
+        ));
+        let swapped = Bip39Share::new(*shares[1].id(), shares[0].secret().clone());
+        assert!(matches!(
+            verify_share(&swapped, &commitment, &proofs),
+            Err(Error::CommitmentMismatch { id: 2 })
+        ));
+
+        // A share without proof.
+        assert!(matches!(
+            verify_share(&shares[3], &commitment, &proofs[..3]),
+            Err(Error::MissingShareProof { id: 4 })
+        ));
+
+        // The shares and proofs of another split do not match the commitment.
+        let (other_shares, other_commitment, other_proofs) = test_split(3);
+        assert!(matches!(
+            verify_share(&other_shares[0], &commitment, &proofs),
+            Err(Error::CommitmentMismatch { id: 1 })
+        ));
+        assert!(matches!(
+            verify_share(&shares[0], &commitment, &other_proofs),
+            Err(Error::CommitmentMismatch { id: 1 })
+        ));
+        assert!(matches!(
+            verify_share(&shares[0], &other_commitment, &proofs),
+            Err(Error::CommitmentMismatch { id: 1 })
+        ));
+
+        // A share of another length.
+        let mut rng = StdRng::seed_from_u64(0);
+        let long = Bip39Secret::generate(24, &mut rng).unwrap();
+        assert!(matches!(
+            verify_share(&Bip39Share::new(1, long), &commitment, &proofs),
+            Err(Error::MismatchedShareLengths)
+        ));
+    }
+
+    #[test]
+    fn verify_corrupted_word() {
+        let dictionary = Bip39Dictionary::builtin(Language::English).unwrap();
+        let (shares, commitment, proofs) = test_split(2);
+
+        // Replace the first word of a share by another word (the checksum is then likely wrong,
+        // but the proof must not depend on it).
+        let mnemonic = shares[2].to_mnemonic(&dictionary);
+        let (first, rest) = mnemonic.split_once(' ').unwrap();
+        let other = if first == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        let corrupted = format!("{other} {rest}");
+        let share = Bip39Share::from_mnemonic(3, &corrupted, &dictionary).unwrap();
+        assert!(matches!(
+            verify_share(&share, &commitment, &proofs),
+            Err(Error::CommitmentMismatch { id: 3 })
+        ));
+    }
+
+    #[test]
+    fn verify_dishonest_dealer() {
+        let (shares, commitment, proofs) = test_split(2);
+
+        // A commitment to 2 (or any other value than a bit) is detected.
+        let mut forged = commitment.clone();
+        forged.bits[0] += RistrettoPoint::generator();
+        assert!(matches!(
+            forged.verify(),
+            Err(Error::InvalidCommitmentProof)
+        ));
+        assert!(matches!(
+            verify_share(&shares[0], &forged, &proofs),
+            Err(Error::InvalidCommitmentProof)
+        ));
+
+        // A share off the committed polynomial (a proof for it cannot be made up from the others).
+        let mut rng = StdRng::seed_from_u64(1);
+        let off = Bip39Share::new(4, Bip39Secret::generate(12, &mut rng).unwrap());
+        assert!(matches!(
+            verify_share(&off, &commitment, &proofs),
+            Err(Error::CommitmentMismatch { id: 4 })
+        ));
+    }
+
+    #[test]
+    fn verify_other_language() {
+        let english = Bip39Dictionary::builtin(Language::English).unwrap();
+        let spanish = Bip39Dictionary::builtin(Language::Spanish).unwrap();
+        let (shares, commitment, proofs) = test_split(2);
+
+        let mnemonic = convert_mnemonic(&shares[0].to_mnemonic(&english), &english, &spanish);
+        let share = Bip39Share::from_mnemonic(1, &mnemonic.unwrap(), &spanish).unwrap();
+        verify_share(&share, &commitment, &proofs).unwrap();
+    }
+
+    #[test]
+    fn parse() {
+        let (shares, commitment, proofs) = test_split(2);
+
+        let string = commitment.to_string();
+        assert_eq!(string.lines().count(), 1 + 256 + 1 + 2 * 2 * 256);
+        assert_eq!(string.parse::<PolynomialCommitment>().unwrap(), commitment);
+        for proof in &proofs {
+            let string = proof.to_string();
+            assert!(string.starts_with(&format!("shamir-bip39-proof-v1 {}\n", proof.id())));
+            let parsed = format!("  {string}\n").parse::<ShareProof>().unwrap();
+            assert_eq!(parsed, *proof);
+        }
+        verify_share(&shares[0], &string.parse().unwrap(), &proofs).unwrap();
+
+        let header = "shamir-bip39-commitment-v1 2 16";
+        let body = string.split_once('\n').unwrap().1;
+        let element = "ab".repeat(32);
+        for invalid in [
+            String::new(),
+            header.to_string(),
+            format!("shamir-bip39-commitment-v2 2 16\n{body}"),
+            format!("shamir-bip39-commitment-v1 0 16\n{body}"),
+            format!("shamir-bip39-commitment-v1 3 16\n{body}"),
+            format!("shamir-bip39-commitment-v1 2 17\n{body}"),
+            format!("shamir-bip39-commitment-v1 2\n{body}"),
+            format!("{header}\n{body}{element}"),
+            format!("{header}\n{}", &body[65..]),
+            format!("{header}\n{}", body.replacen(&body[..2], "zz", 1)),
+            format!("{header}\n{}", body.replacen(&body[..2], "é", 1)),
+            // A point that is not a valid encoding.
+            format!("{header}\n{}{}", "ff".repeat(32), &body[64..]),
+        ] {
+            assert!(
+                matches!(
+                    invalid.parse::<PolynomialCommitment>(),
+                    Err(Error::InvalidCommitment)
+                ),
+                "{invalid:?}"
+            );
+        }
+
+        // A scalar that is not reduced.
+        let proof = proofs[0].to_string();
+        let (header, body) = proof.split_once('\n').unwrap();
+        for invalid in [
+            String::new(),
+            header.to_string() + "\n" + &element,
+            format!("shamir-bip39-proof-v1 0\n{body}"),
+            format!("shamir-bip39-proof-v1 256\n{body}"),
+            format!("shamir-bip39-proof-v1\n{body}"),
+            format!("{header}\n{body}{element}"),
+            format!("{header}\n{}{}", "ff".repeat(32), &body[64..]),
+        ] {
+            assert!(
+                matches!(invalid.parse::<ShareProof>(), Err(Error::InvalidShareProof)),
+                "{invalid:?}"
+            );
+        }
+    }
+}
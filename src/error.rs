@@ -0,0 +1,311 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, io};
+
+use crate::{bip39::Language, physical::EntropySource};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The errors of the library. Variants about a word of a mnemonic carry the (zero-based) index of
+/// the word in the mnemonic when it is known. New variants may be added without a breaking change,
+/// so matches on the errors need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The dictionary file could not be read.
+    Io(io::Error),
+    /// The embedded wordlist of a language does not match the digest of the official list.
+    CorruptedDictionary(Language),
+    /// The dictionary does not contain the expected number of words.
+    InvalidDictionaryLength { expected: usize, actual: usize },
+    /// A line of the dictionary is empty.
+    EmptyDictionaryWord { line: usize },
+    /// A word of the dictionary contains whitespace.
+    DictionaryWordWithWhitespace { line: usize, word: String },
+    /// A word appears twice in the dictionary.
+    DuplicateDictionaryWord {
+        first_line: usize,
+        line: usize,
+        word: String,
+    },
+    /// No official wordlist contains the words of a mnemonic.
+    UnknownLanguage { mnemonic: usize },
+    /// The mnemonics do not all use the same wordlist.
+    MixedLanguages,
+    /// The words of the mnemonics fit several wordlists.
+    AmbiguousLanguage(Vec<Language>),
+    /// A word is not in the dictionary (the closest dictionary words are suggested).
+    InvalidWord {
+        index: Option<usize>,
+        word: String,
+        suggestions: Vec<String>,
+    },
+    /// A word prefix matches several words of the dictionary.
+    AmbiguousWord {
+        index: Option<usize>,
+        word: String,
+        matches: Vec<String>,
+    },
+    /// The mnemonic does not have a valid number of words.
+    InvalidMnemonicLength {
+        expected: &'static [usize],
+        actual: usize,
+    },
+    /// The bits do not have a valid entropy length.
+    InvalidEntropyLength { actual: usize },
+    /// The bits do not have a valid checksum length.
+    InvalidChecksumLength { actual: usize },
+    /// The checksum of the mnemonic does not match its entropy.
+    InvalidChecksum,
+    /// Fewer random bits were collected than the mnemonic requires.
+    NotEnoughEntropy { expected: usize, actual: usize },
+    /// The stems of the mnemonic do not identify its words.
+    AmbiguousStems,
+    /// The mnemonic has no missing words to recover.
+    NoMissingWords,
+    /// The mnemonic has more missing words than can be recovered.
+    TooManyMissingWords { max: usize, actual: usize },
+    /// The recovery would try more candidate mnemonics than allowed.
+    TooManyCandidates { max: usize, actual: usize },
+    /// The mnemonic has more misspelled words than can be corrected.
+    TooManyMisspelledWords { max: usize, actual: usize },
+    /// No shares are provided (or requested).
+    NoShares,
+    /// The threshold is zero.
+    ZeroThreshold,
+    /// The threshold is greater than the number of shares.
+    ThresholdTooHigh { threshold: u8, shares: u8 },
+    /// Fewer shares are provided than required.
+    NotEnoughShares { expected: usize, actual: usize },
+    /// The shares do not all have the same number of words.
+    MismatchedShareLengths,
+    /// A share has ID 0 (the x-coordinate of the secret itself).
+    ZeroShareId,
+    /// Several shares have the same ID.
+    DuplicateShareId { id: u8 },
+    /// The shares disagree on the secret in a way that cannot be corrected by discarding up to
+    /// `max` of them.
+    TooManyCorruptedShares { max: usize },
+    /// A polynomial commitment is not formatted as written at split time (the input is not echoed
+    /// since it may be a share given by mistake).
+    InvalidCommitment,
+    /// A share proof is not formatted as written at split time.
+    InvalidShareProof,
+    /// The polynomial commitment does not prove that it commits to bits (a faulty or dishonest
+    /// dealer).
+    InvalidCommitmentProof,
+    /// No proof has the ID of the share.
+    MissingShareProof { id: u8 },
+    /// The proof is for a share with another ID.
+    ShareIdMismatch { share: u8, proof: u8 },
+    /// The share does not lie on the committed polynomial.
+    CommitmentMismatch { id: u8 },
+    /// An outcome of a physical source of randomness is not valid.
+    InvalidOutcome {
+        source: EntropySource,
+        index: usize,
+        outcome: String,
+    },
+    /// A card appears twice in the same deck.
+    DuplicateCard { deck: usize, card: String },
+}
+
+impl Error {
+    /// Set the index of the word (in the mnemonic) that caused the error, if any.
+    pub(crate) fn at_word(self, i: usize) -> Self {
+        match self {
+            Self::InvalidWord {
+                word, suggestions, ..
+            } => Self::InvalidWord {
+                index: Some(i),
+                word,
+                suggestions,
+            },
+            Self::AmbiguousWord { word, matches, .. } => Self::AmbiguousWord {
+                index: Some(i),
+                word,
+                matches,
+            },
+            error => error,
+        }
+    }
+}
+
+/// Describe a word, with its position in the mnemonic if known.
+fn word_position(index: &Option<usize>, word: &str) -> String {
+    match index {
+        Some(i) => format!("word #{} '{word}'", i + 1),
+        None => format!("word '{word}'"),
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Failed to read the BIP-39 dictionary: {e}"),
+            Self::CorruptedDictionary(language) => write!(
+                f,
+                "The embedded BIP-39 {language:?} dictionary does not match the official wordlist"
+            ),
+            Self::InvalidDictionaryLength { expected, actual } => {
+                write!(f, "Invalid BIP-39 dictionary length {actual} != {expected}")
+            }
+            Self::EmptyDictionaryWord { line } => {
+                write!(f, "Empty BIP-39 dictionary word at line {line}")
+            }
+            Self::DictionaryWordWithWhitespace { line, word } => write!(
+                f,
+                "BIP-39 dictionary word {word:?} at line {line} contains whitespace"
+            ),
+            Self::DuplicateDictionaryWord {
+                first_line,
+                line,
+                word,
+            } => write!(
+                f,
+                "Duplicate BIP-39 dictionary word '{word}' at lines {first_line} and {line}"
+            ),
+            Self::UnknownLanguage { mnemonic } => write!(
+                f,
+                "No BIP-39 wordlist contains the words of mnemonic #{}",
+                mnemonic + 1
+            ),
+            Self::MixedLanguages => {
+                write!(f, "The mnemonics do not all use the same BIP-39 wordlist")
+            }
+            Self::AmbiguousLanguage(languages) => write!(
+                f,
+                "Ambiguous BIP-39 wordlist, the words fit the lists {languages:?}"
+            ),
+            Self::InvalidWord {
+                index,
+                word,
+                suggestions,
+            } => {
+                write!(f, "Invalid BIP-39 {}", word_position(index, word))?;
+                if !suggestions.is_empty() {
+                    write!(f, ", did you mean: {}?", suggestions.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::AmbiguousWord {
+                index,
+                word,
+                matches,
+            } => write!(
+                f,
+                "Ambiguous BIP-39 {} prefix, it matches: {}",
+                word_position(index, word),
+                matches.join(", ")
+            ),
+            Self::InvalidMnemonicLength { expected, actual } => write!(
+                f,
+                "Invalid mnemonic length {actual}, expected one of {expected:?} words"
+            ),
+            Self::InvalidEntropyLength { actual } => {
+                write!(f, "Invalid entropy length {actual} bits")
+            }
+            Self::InvalidChecksumLength { actual } => {
+                write!(f, "Invalid checksum length {actual} bits")
+            }
+            Self::InvalidChecksum => write!(f, "Invalid checksum"),
+            Self::NotEnoughEntropy { expected, actual } => write!(
+                f,
+                "Not enough entropy, collected {actual} bits but {expected} bits are needed"
+            ),
+            Self::AmbiguousStems => {
+                write!(f, "The stems do not identify the words of the mnemonic")
+            }
+            Self::NoMissingWords => write!(f, "The mnemonic has no missing words"),
+            Self::TooManyMissingWords { max, actual } => {
+                write!(f, "Too many missing words to recover ({actual} > {max})")
+            }
+            Self::TooManyCandidates { max, actual } => {
+                write!(f, "Too many candidates to search ({actual} > {max})")
+            }
+            Self::TooManyMisspelledWords { max, actual } => {
+                write!(f, "Too many misspelled words to correct ({actual} > {max})")
+            }
+            Self::NoShares => write!(f, "There must be at least one share"),
+            Self::ZeroThreshold => write!(f, "The threshold must be at least one"),
+            Self::ThresholdTooHigh { threshold, shares } => write!(
+                f,
+                "The threshold ({threshold}) must not exceed the total shares ({shares})"
+            ),
+            Self::NotEnoughShares { expected, actual } => write!(
+                f,
+                "At least {expected} shares are required, but only {actual} are provided"
+            ),
+            Self::MismatchedShareLengths => {
+                write!(f, "All shares must have the same number of words")
+            }
+            Self::ZeroShareId => write!(f, "Share IDs must be between 1 and 255"),
+            Self::DuplicateShareId { id } => {
+                write!(
+                    f,
+                    "All shares must have distinct IDs, ID {id} appears twice"
+                )
+            }
+            Self::TooManyCorruptedShares { max } => write!(
+                f,
+                "The shares are inconsistent and cannot be corrected (at most {max} corrupted \
+                shares can be)"
+            ),
+            Self::InvalidCommitment => {
+                write!(
+                    f,
+                    "Invalid polynomial commitment, expected the file written by split"
+                )
+            }
+            Self::InvalidShareProof => {
+                write!(f, "Invalid share proof, expected a file written by split")
+            }
+            Self::InvalidCommitmentProof => write!(
+                f,
+                "The polynomial commitment is not to bits (faulty or dishonest dealer)"
+            ),
+            Self::MissingShareProof { id } => write!(f, "No proof for the share with ID {id}"),
+            Self::ShareIdMismatch { share, proof } => write!(
+                f,
+                "The share has ID {share} but the proof is for the share with ID {proof}"
+            ),
+            Self::CommitmentMismatch { id } => write!(
+                f,
+                "The share with ID {id} does not lie on the committed polynomial (corrupted or \
+                mistyped share, or inconsistent dealer)"
+            ),
+            Self::InvalidOutcome {
+                source,
+                index,
+                outcome,
+            } => {
+                let name = match source {
+                    EntropySource::Coin => "coin flip",
+                    EntropySource::D6 => "d6 roll",
+                    EntropySource::D20 => "d20 roll",
+                    EntropySource::Cards => "card",
+                };
+                write!(f, "Invalid {name} #{} '{outcome}'", index + 1)
+            }
+            Self::DuplicateCard { deck, card } => {
+                write!(f, "Card '{card}' appears twice in deck #{}", deck + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
@@ -1,53 +1,161 @@
 // Copyright (c) Alberto Sonnino
 // SPDX-License-Identifier: Apache-2.0
 
-use gf256::gf256;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub};
+
 use rand::{CryptoRng, Rng, RngCore};
 
-use crate::shamir::{Random, ShamirPolynomial, ShamirSecretSharing, ShamirShare, Zero};
+use crate::{
+    error::Result,
+    shamir::{
+        check_parameters,
+        check_shares,
+        lagrange_coefficients,
+        Random,
+        ShamirPolynomial,
+        ShamirSecretSharing,
+        ShamirShare,
+        Zero,
+    },
+};
+
+/// The low byte of the reduction polynomial x^8 + x^4 + x^3 + x^2 + 1 (0x11d), the same as the
+/// `gf256` crate so that shares are unchanged.
+const POLYNOMIAL: u8 = 0x1d;
+
+/// An element of the field GF(256). The arithmetic runs in constant time: it never branches on
+/// or indexes memory with the value of an element, so it does not leak secrets through timing or
+/// cache side channels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gf256(pub(crate) u8);
+
+impl Gf256 {
+    /// Get the multiplicative inverse of the element as a^254 (by Fermat's little theorem), using
+    /// a fixed sequence of squarings and multiplications. The inverse of zero is zero.
+    pub fn inverse(self) -> Self {
+        // a^254 = a^2 * a^4 * ... * a^128.
+        let mut square = self;
+        let mut inverse = Self(1);
+        for _ in 0..7 {
+            square *= square;
+            inverse *= square;
+        }
+        inverse
+    }
+}
+
+impl Add for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Sub for Gf256 {
+    type Output = Self;
 
-impl Zero for gf256 {
+    /// Subtract an element, which is the same as adding it in a field of characteristic 2.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl AddAssign for Gf256 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul for Gf256 {
+    type Output = Self;
+
+    /// Multiply two elements by shifting and adding (carry-less), selecting the terms with masks
+    /// rather than branches.
+    fn mul(self, rhs: Self) -> Self {
+        let (mut a, b) = (self.0, rhs.0);
+        let mut product = 0;
+        for i in 0..8 {
+            // Add `a` if the bit `i` of `b` is set.
+            product ^= a & 0u8.wrapping_sub((b >> i) & 1);
+            // Multiply `a` by x, reducing by the polynomial if the top bit overflows.
+            let overflow = 0u8.wrapping_sub(a >> 7);
+            a = (a << 1) ^ (overflow & POLYNOMIAL);
+        }
+        Self(product)
+    }
+}
+
+impl MulAssign for Gf256 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for Gf256 {
+    type Output = Self;
+
+    /// Divide by an element (dividing by zero yields zero).
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl From<u8> for Gf256 {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Gf256> for u8 {
+    fn from(value: Gf256) -> Self {
+        value.0
+    }
+}
+
+impl Zero for Gf256 {
     fn zero() -> Self {
-        gf256(0)
+        Self(0)
     }
 }
 
-impl Random for gf256 {
+impl Random for Gf256 {
     fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
-        gf256(rng.random_range(1..=255))
+        Self(rng.random())
     }
 }
 
-impl ShamirSecretSharing for gf256 {
-    fn split<R: CryptoRng + RngCore>(&self, n: u8, t: u8, rng: &mut R) -> Vec<ShamirShare<Self>> {
-        assert!(n > 0, "There must be at least one share");
-        assert!(t > 0, "The threshold must be at least one");
-        assert!(t <= n, "The threshold must be lower than the total shares");
+impl ShamirSecretSharing for Gf256 {
+    fn split<R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<Vec<ShamirShare<Self>>> {
+        check_parameters(n, t)?;
 
         let polynomial = ShamirPolynomial::random(*self, t - 1, rng);
 
-        (1..=n)
+        Ok((1..=n)
             .map(|id| {
-                let secret = polynomial.evaluate(gf256(id));
+                let secret = polynomial.evaluate(Gf256(id));
                 ShamirShare::new(id, secret)
             })
-            .collect()
-    }
-
-    fn reconstruct<S: AsRef<ShamirShare<Self>>>(shares: &[S]) -> Self {
-        let mut y = gf256(0);
-        for (i, share) in shares.iter().enumerate() {
-            let mut li = gf256(1);
-            let (x0, y0) = share.as_ref().as_coordinates();
-            for (j, share) in shares.iter().enumerate() {
-                let (x1, _y1) = share.as_ref().as_coordinates();
-                if i != j {
-                    li *= gf256(*x1) / (gf256(*x0) + gf256(*x1));
-                }
-            }
-            y += li * y0;
+            .collect())
+    }
+
+    fn reconstruct<S: AsRef<ShamirShare<Self>>>(shares: &[S]) -> Result<Self> {
+        check_shares(shares)?;
+        let ids = shares.iter().map(|s| *s.as_ref().id()).collect::<Vec<_>>();
+
+        let mut y = Gf256(0);
+        for (li, share) in lagrange_coefficients::<Self>(&ids).into_iter().zip(shares) {
+            y += li * *share.as_ref().secret();
         }
-        y
+        Ok(y)
     }
 }
 
@@ -55,22 +163,216 @@ impl ShamirSecretSharing for gf256 {
 /// to prevent collisions.
 #[cfg(test)]
 mod test {
-    use gf256::gf256;
+    use std::{hint::black_box, iter::once, time::Instant};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
-    use crate::shamir;
+    use super::Gf256;
+    use crate::shamir::{self, Random, ShamirSecretSharing, Zero};
+
+    /// The number of samples drawn for each possible value (or tuple of values) in the statistical
+    /// tests.
+    const SAMPLES_PER_VALUE: usize = 64;
+    const SAMPLES_PER_TUPLE: usize = 16;
+    /// The standard normal quantile of the chi-squared tests, at p = 10^-4.
+    const NORMAL_QUANTILE: f64 = 3.719;
+
+    /// The chi-squared statistic of the counts of each value against the uniform distribution.
+    fn chi_squared(counts: &[usize]) -> f64 {
+        let expected = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    /// The critical value of the chi-squared test with the given degrees of freedom, with the
+    /// Wilson-Hilferty approximation.
+    fn chi_squared_critical(degrees: usize) -> f64 {
+        let k = degrees as f64;
+        k * (1.0 - 2.0 / (9.0 * k) + NORMAL_QUANTILE * (2.0 / (9.0 * k)).sqrt()).powi(3)
+    }
+
+    #[test]
+    fn arithmetic() {
+        // The field matches the `gf256` crate (and thus the shares it produced).
+        for a in 0..=255 {
+            for b in 0..=255 {
+                let expected = ::gf256::gf256(a) * ::gf256::gf256(b);
+                assert_eq!((Gf256(a) * Gf256(b)).0, expected.0);
+                assert_eq!((Gf256(a) + Gf256(b)).0, a ^ b);
+                if b != 0 {
+                    let expected = ::gf256::gf256(a) / ::gf256::gf256(b);
+                    assert_eq!((Gf256(a) / Gf256(b)).0, expected.0);
+                }
+            }
+        }
+
+        for a in 1..=255 {
+            assert_eq!(Gf256(a) * Gf256(a).inverse(), Gf256(1));
+        }
+        assert_eq!(Gf256::zero().inverse(), Gf256::zero());
+    }
 
     #[test]
     fn reconstruct() {
-        shamir::test::test_reconstruct::<gf256>();
+        shamir::test::test_reconstruct::<Gf256>();
     }
 
     #[test]
     fn reconstruct_sparse() {
-        shamir::test::test_reconstruct_sparse::<gf256>();
+        shamir::test::test_reconstruct_sparse::<Gf256>();
     }
 
     #[test]
     fn reconstruct_missing_shares() {
-        shamir::test::test_reconstruct_missing_shares::<gf256>();
+        shamir::test::test_reconstruct_missing_shares::<Gf256>();
+    }
+
+    #[test]
+    fn reconstruct_invalid_shares() {
+        shamir::test::test_reconstruct_invalid_shares::<Gf256>();
+    }
+
+    #[test]
+    fn random_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut counts = [0; 256];
+        for _ in 0..256 * SAMPLES_PER_VALUE {
+            counts[Gf256::random(&mut rng).0 as usize] += 1;
+        }
+        assert!(counts[0] > 0);
+        assert!(chi_squared(&counts) < chi_squared_critical(255));
+    }
+
+    #[test]
+    fn shares_are_uniform() {
+        // With threshold `t`, the first `t - 1` shares are jointly uniform over all the tuples of
+        // values whatever the secret, so they reveal nothing about it.
+        let mut rng = StdRng::seed_from_u64(0);
+        for t in 2..=3u8 {
+            let tuples = 1 << (8 * (t as usize - 1));
+            for secret in [0, 0x5a, 0xff] {
+                let mut counts = vec![0; tuples];
+                for _ in 0..tuples * SAMPLES_PER_TUPLE {
+                    let shares = Gf256(secret).split(t, t, &mut rng).unwrap();
+                    let tuple = shares[..t as usize - 1]
+                        .iter()
+                        .fold(0, |tuple, share| (tuple << 8) | share.secret().0 as usize);
+                    counts[tuple] += 1;
+                }
+                assert!(chi_squared(&counts) < chi_squared_critical(tuples - 1));
+            }
+        }
+    }
+
+    #[test]
+    fn split_invalid_parameters() {
+        shamir::test::test_split_invalid_parameters::<Gf256>();
+    }
+
+    /// Welch's t-statistic of the difference between the means of two samples.
+    fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+        let moments = |x: &[f64]| {
+            let mean = x.iter().sum::<f64>() / x.len() as f64;
+            let variance = x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (x.len() - 1) as f64;
+            (mean, variance / x.len() as f64)
+        };
+        let ((mean_a, var_a), (mean_b, var_b)) = (moments(a), moments(b));
+        (mean_a - mean_b) / (var_a + var_b).sqrt()
+    }
+
+    /// The number of timed batches, after as many batches warming up the caches and the branch
+    /// predictors.
+    const MEASUREMENTS: usize = 50_000;
+    /// The number of operations per timed batch, so that a batch takes microseconds (well above
+    /// the resolution of the timer).
+    const BATCH: usize = 1024;
+    /// The number of percentile cuts of the timings (as in dudect).
+    const CUTS: usize = 100;
+    /// The |t| above which the timings of the two classes differ: dudect reports a leak above 10.
+    const LEAK_THRESHOLD: f64 = 10.0;
+
+    /// A named operation on the bytes of two elements, whose timing is tested.
+    type Operation = (&'static str, fn(u8, u8) -> u8);
+
+    /// Time an operation on inputs of two classes (a fixed input and random inputs), interleaved
+    /// at random, and return the largest Welch's t-statistic between the timings of the two
+    /// classes, over the uncropped timings and the timings cropped at each percentile cut (to
+    /// remove the noise of interrupts and context switches), as in dudect.
+    fn timing_leak<F: Fn(u8, u8) -> u8>(operation: F) -> f64 {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut timings = [Vec::new(), Vec::new()];
+        let mut inputs = [(0, 0); BATCH];
+        for i in 0..2 * MEASUREMENTS {
+            // Draw the inputs of both classes, so that preparing them takes the same time.
+            let class = rng.random_range(0..2);
+            for input in inputs.iter_mut() {
+                *input = (rng.random(), rng.random());
+            }
+            if class == 0 {
+                inputs = [(0, 0); BATCH];
+            }
+
+            let start = Instant::now();
+            for (a, b) in black_box(&inputs) {
+                black_box(operation(black_box(*a), black_box(*b)));
+            }
+            let elapsed = start.elapsed().as_nanos() as f64;
+            if i >= MEASUREMENTS {
+                timings[class].push(elapsed);
+            }
+        }
+
+        let mut sorted = timings.concat();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let cutoffs = (0..CUTS).map(|i| {
+            let percentile = 1.0 - 0.5f64.powf(10.0 * (i + 1) as f64 / CUTS as f64);
+            sorted[(percentile * sorted.len() as f64) as usize]
+        });
+        once(f64::INFINITY)
+            .chain(cutoffs)
+            .map(|cutoff| {
+                let [fixed, random] = timings
+                    .clone()
+                    .map(|t| t.into_iter().filter(|t| *t <= cutoff).collect::<Vec<_>>());
+                welch_t(&fixed, &random).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Run with `cargo test --release -- --ignored constant_time` on an otherwise idle machine.
+    #[test]
+    #[ignore = "timing"]
+    fn constant_time() {
+        let operations: [Operation; 3] = [
+            ("multiplication", |a, b| (Gf256(a) * Gf256(b)).0),
+            ("inversion", |a, _| Gf256(a).inverse().0),
+            ("division", |a, b| (Gf256(a) / Gf256(b)).0),
+        ];
+        for (name, operation) in operations {
+            let t = timing_leak(operation);
+            assert!(t < LEAK_THRESHOLD, "Timing leak in {name} (t = {t:.2})");
+        }
+    }
+
+    /// Check that the timing test detects the table-based (and branching) arithmetic of the `gf256`
+    /// crate.
+    #[test]
+    #[ignore = "timing"]
+    fn constant_time_detects_leaks() {
+        let operations: [Operation; 2] = [
+            ("multiplication", |a, b| {
+                (::gf256::gf256(a) * ::gf256::gf256(b)).0
+            }),
+            ("division", |a, b| {
+                let b = if b == 0 { 1 } else { b };
+                (::gf256::gf256(a) / ::gf256::gf256(b)).0
+            }),
+        ];
+        for (name, operation) in operations {
+            let t = timing_leak(operation);
+            assert!(t > LEAK_THRESHOLD, "No timing leak in {name} (t = {t:.2})");
+        }
     }
 }
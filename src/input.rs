@@ -0,0 +1,196 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs::read_to_string,
+    io::{stdin, Read},
+    mem,
+    str::FromStr,
+};
+
+use color_eyre::owo_colors::OwoColorize;
+use eyre::{ensure, Result, WrapErr};
+use shamir_bip39::{PolynomialCommitment, ShareProof};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The path designating the standard input instead of a file.
+const STDIN_PATH: &str = "-";
+/// The capacity reserved to read the standard input, enough for dozens of 24-word shares.
+const STDIN_CAPACITY: usize = 1 << 16;
+
+/// A share as provided by the user, in the format "INDEX WORD_1 .. WORD_N". The words are wiped
+/// when the share is dropped.
+#[derive(Clone)]
+pub struct ShareString {
+    pub index: u8,
+    pub secret: String,
+}
+
+impl Drop for ShareString {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl FromStr for ShareString {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(' ');
+        let index = parts.next().unwrap().parse()?;
+        let secret = parts.collect::<Vec<_>>().join(" ");
+        Ok(Self { index, secret })
+    }
+}
+
+/// Read a secret (described as e.g. "secret mnemonic") from the command line, from a file (or the
+/// standard input if the path is '-'), or else from an interactive prompt that does not echo the
+/// input.
+pub fn read_secret(
+    argument: Option<String>,
+    path: Option<&str>,
+    description: &str,
+) -> Result<Zeroizing<String>> {
+    let secret = match (argument, path) {
+        (Some(secret), _) => {
+            warn_argument_secret();
+            Zeroizing::new(secret)
+        }
+        (None, Some(path)) => Zeroizing::new(read_input(path)?.trim().to_string()),
+        (None, None) => {
+            let mut prompt = description.to_string();
+            prompt[..1].make_ascii_uppercase();
+            Zeroizing::new(rpassword::prompt_password(format!("{prompt}: "))?)
+        }
+    };
+    ensure!(!secret.trim().is_empty(), "No {description} provided");
+    Ok(secret)
+}
+
+/// Read a share in the format "INDEX WORD_1 .. WORD_N" like [`read_secret`].
+pub fn read_share(argument: Option<ShareString>, path: Option<&str>) -> Result<ShareString> {
+    match argument {
+        Some(share) => {
+            warn_argument_secret();
+            Ok(share)
+        }
+        None => read_secret(None, path, "share")?
+            .trim()
+            .parse()
+            .wrap_err("Invalid share"),
+    }
+}
+
+/// Read the mnemonic of a secret or of a share like [`read_secret`] and [`read_share`], and return
+/// it with the ID of the share (if it is one). If neither is provided, prompt for either: the
+/// input is a share if it starts with its ID.
+pub fn read_mnemonic_or_share(
+    mnemonic: Option<String>,
+    mnemonic_path: Option<&str>,
+    share: Option<ShareString>,
+    share_path: Option<&str>,
+) -> Result<(Option<u8>, Zeroizing<String>)> {
+    let mut share = match (mnemonic, mnemonic_path, share, share_path) {
+        (None, None, None, None) => {
+            let input = read_secret(None, None, "mnemonic (or share, starting with its ID)")?;
+            match input.split_whitespace().next().map(str::parse::<u8>) {
+                Some(Ok(_)) => input
+                    .trim()
+                    .parse::<ShareString>()
+                    .wrap_err("Invalid share")?,
+                _ => return Ok((None, input)),
+            }
+        }
+        (None, None, share, share_path) => read_share(share, share_path)?,
+        (mnemonic, mnemonic_path, _, _) => {
+            return Ok((None, read_secret(mnemonic, mnemonic_path, "mnemonic")?));
+        }
+    };
+    Ok((Some(share.index), mem::take(&mut share.secret).into()))
+}
+
+/// Read shares from the command line, from a file with one share per line (or the standard input
+/// if the path is '-'), or else from interactive prompts that do not echo the input (until an
+/// empty line is entered).
+pub fn read_shares(arguments: Vec<ShareString>, path: Option<&str>) -> Result<Vec<ShareString>> {
+    if !arguments.is_empty() {
+        warn_argument_secret();
+        return Ok(arguments);
+    }
+
+    let shares = match path {
+        Some(path) => read_input(path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                line.trim()
+                    .parse()
+                    .wrap_err_with(|| format!("Invalid share on line {}", i + 1))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => {
+            let mut shares = Vec::new();
+            loop {
+                let prompt = format!("Share #{} (empty to finish): ", shares.len() + 1);
+                let line = Zeroizing::new(rpassword::prompt_password(prompt)?);
+                if line.trim().is_empty() {
+                    break;
+                }
+                let share = line.trim().parse().wrap_err("Invalid share")?;
+                shares.push(share);
+            }
+            shares
+        }
+    };
+    ensure!(!shares.is_empty(), "There must be at least one share");
+    Ok(shares)
+}
+
+/// Read the commitment to the polynomial of a split from a file (or the standard input if the
+/// path is '-').
+pub fn read_commitment(path: &str) -> Result<PolynomialCommitment> {
+    read_input(path)?
+        .parse()
+        .wrap_err_with(|| format!("Invalid commitment in {path}"))
+}
+
+/// Read the proofs of the shares from files, one proof per file.
+pub fn read_proofs(paths: &[String]) -> Result<Vec<ShareProof>> {
+    paths
+        .iter()
+        .map(|path| {
+            read_input(path)?
+                .parse()
+                .wrap_err_with(|| format!("Invalid share proof in {path}"))
+        })
+        .collect()
+}
+
+/// Warn that a secret passed as a command line argument is saved in the shell history and visible
+/// to the other processes of the machine.
+fn warn_argument_secret() {
+    eprintln!(
+        "{} {}",
+        "WARNING:".red().bold(),
+        "Secrets passed as command line arguments are saved in the shell history and visible to \
+        other processes (e.g., in /proc/*/cmdline). Read them from a file, the standard input \
+        ('-'), or the interactive prompt instead."
+            .red()
+    );
+}
+
+/// Read the content of a file, or of the standard input if the path is '-'.
+fn read_input(path: &str) -> Result<Zeroizing<String>> {
+    if path == STDIN_PATH {
+        // Reserve enough space for the input not to be reallocated (leaving unwiped copies).
+        let mut content = Zeroizing::new(String::with_capacity(STDIN_CAPACITY));
+        stdin()
+            .read_to_string(&mut content)
+            .wrap_err("Failed to read the standard input")?;
+        Ok(content)
+    } else {
+        let content = read_to_string(path).wrap_err_with(|| format!("Failed to read {path}"))?;
+        Ok(Zeroizing::new(content))
+    }
+}
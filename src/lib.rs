@@ -0,0 +1,173 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+//! Apply Shamir's secret sharing to bip-39 mnemonics, following EIP-3450. Each share is itself a
+//! valid bip-39 mnemonic (of the same length as the secret) identified by its ID.
+//!
+//! ```
+//! use shamir_bip39::{Bip39Dictionary, Language};
+//!
+//! let dictionary = Bip39Dictionary::builtin(Language::English)?;
+//! let mnemonic = "permit universe parent weapon amused modify essay borrow tobacco budget walnut \
+//!     lunch consider gallery ride amazing frog forget treat market chapter velvet useless topple";
+//!
+//! // Split the mnemonic into 3 shares, any 2 of which reconstruct it.
+//! let shares = shamir_bip39::split(mnemonic, 3, 2, &dictionary, &mut rand::rng())?;
+//! for share in &shares {
+//!     println!("{} {}", share.id(), share.to_mnemonic(&dictionary));
+//! }
+//!
+//! let secret = shamir_bip39::reconstruct(&shares[1..])?;
+//! assert_eq!(secret.to_mnemonic(&dictionary), mnemonic);
+//! # Ok::<(), shamir_bip39::Error>(())
+//! ```
+
+mod bip39;
+mod commitment;
+mod error;
+mod gf256;
+mod memory;
+mod physical;
+mod shamir;
+mod utils;
+
+use rand::{CryptoRng, RngCore};
+
+pub use crate::{
+    bip39::{
+        convert_mnemonic,
+        entropy_bits,
+        Bip39Dictionary,
+        Bip39Secret,
+        Bip39Share,
+        Language,
+        MAX_RECOVERY_CANDIDATES,
+    },
+    commitment::{verify_share, PolynomialCommitment, ShareProof},
+    error::{Error, Result},
+    physical::EntropySource,
+    shamir::{ShamirSecretSharing, ShamirShare},
+};
+/// The field arithmetic underlying the secret sharing, exposed for the benchmarks only (it is not
+/// part of the stable API).
+#[cfg(feature = "internals")]
+#[doc(hidden)]
+pub use crate::{
+    gf256::Gf256,
+    shamir::{FieldArray, Random, Zero},
+};
+
+/// Split a bip-39 mnemonic into `n` shares, any `t` of which can reconstruct it. Fail if the
+/// mnemonic is not valid (including its checksum) or if `t` is not between 1 and `n`. Note that
+/// with `t = 1` every share encodes the mnemonic itself, which offers no protection.
+pub fn split<R: CryptoRng + RngCore>(
+    mnemonic: &str,
+    n: u8,
+    t: u8,
+    dictionary: &Bip39Dictionary,
+    rng: &mut R,
+) -> Result<Vec<Bip39Share>> {
+    let secret = Bip39Secret::from_mnemonic(mnemonic, dictionary)?;
+    secret.is_valid()?;
+    secret.split(n, t, rng)
+}
+
+/// Split a bip-39 mnemonic like [`split`], and also commit to the polynomial the shares lie on for
+/// verifiable secret sharing. Return the shares, the public commitment, and the (private) proof
+/// of each share in the same order: give each shareholder their share and its proof, and publish
+/// the commitment so that they can check their share with [`verify_share`].
+///
+/// ```
+/// use shamir_bip39::{Bip39Dictionary, Language};
+///
+/// let dictionary = Bip39Dictionary::builtin(Language::English)?;
+/// let mnemonic = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+///
+/// let (shares, commitment, proofs) =
+///     shamir_bip39::split_verifiable(mnemonic, 3, 2, &dictionary, &mut rand::rng())?;
+/// for share in &shares {
+///     shamir_bip39::verify_share(share, &commitment, &proofs)?;
+/// }
+/// # Ok::<(), shamir_bip39::Error>(())
+/// ```
+pub fn split_verifiable<R: CryptoRng + RngCore>(
+    mnemonic: &str,
+    n: u8,
+    t: u8,
+    dictionary: &Bip39Dictionary,
+    rng: &mut R,
+) -> Result<(Vec<Bip39Share>, PolynomialCommitment, Vec<ShareProof>)> {
+    let secret = Bip39Secret::from_mnemonic(mnemonic, dictionary)?;
+    secret.is_valid()?;
+    secret.split_verifiable(n, t, rng)
+}
+
+/// Reconstruct a bip-39 secret from (at least the threshold number of) its shares. Fail if no
+/// shares are provided, if a share is not valid, if two shares have the same ID (or a share has ID
+/// 0), or if the shares have different lengths.
+///
+/// ```
+/// use shamir_bip39::{Bip39Dictionary, Bip39Share, Language};
+///
+/// let dictionary = Bip39Dictionary::builtin(Language::English)?;
+/// let shares = [
+///     (3, "sleep bronze innocent property vital flip alley because myth brass prepare upset \
+///         battle valve region glory together erase example hill load walk hollow fiscal"),
+///     (2, "blouse envelope shift ghost burger hip become behind sadness bus own tennis amount \
+///         twelve rabbit doctor mean rice bitter case thumb vital bread frog"),
+/// ]
+/// .into_iter()
+/// .map(|(id, mnemonic)| Bip39Share::from_mnemonic(id, mnemonic, &dictionary))
+/// .collect::<shamir_bip39::Result<Vec<_>>>()?;
+///
+/// let secret = shamir_bip39::reconstruct(&shares)?;
+/// assert!(secret.to_mnemonic(&dictionary).starts_with("permit universe parent weapon"));
+/// # Ok::<(), shamir_bip39::Error>(())
+/// ```
+pub fn reconstruct(shares: &[Bip39Share]) -> Result<Bip39Secret> {
+    for share in shares {
+        share.is_valid()?;
+    }
+    Bip39Secret::reconstruct(shares)
+}
+
+/// Reconstruct a bip-39 secret split with threshold `t` from `n` shares of which up to
+/// `(n - t) / 2` may be corrupted, and return the (sorted) IDs of the shares that disagree with
+/// it. Unlike [`reconstruct`], which silently returns another valid-looking secret when a share
+/// is wrong, this fails if the shares are inconsistent beyond correction. Too many corrupted
+/// shares are only guaranteed to be detected (rather than corrected into another secret) if at
+/// most `n - t - (n - t) / 2` shares are corrupted, e.g., one share among `t + 1`.
+///
+/// ```
+/// use shamir_bip39::{Bip39Dictionary, Bip39Share, Language};
+///
+/// let dictionary = Bip39Dictionary::builtin(Language::English)?;
+/// let mnemonic = "permit universe parent weapon amused modify essay borrow tobacco budget walnut \
+///     lunch consider gallery ride amazing frog forget treat market chapter velvet useless topple";
+/// let mut shares = shamir_bip39::split(mnemonic, 5, 3, &dictionary, &mut rand::rng())?;
+///
+/// // Mix up the third share with the first one.
+/// shares[2] = Bip39Share::from_mnemonic(3, &shares[0].to_mnemonic(&dictionary), &dictionary)?;
+///
+/// let (secret, corrupted) = shamir_bip39::reconstruct_with_errors(&shares, 3)?;
+/// assert_eq!(secret.to_mnemonic(&dictionary), mnemonic);
+/// assert_eq!(corrupted, [3]);
+/// # Ok::<(), shamir_bip39::Error>(())
+/// ```
+pub fn reconstruct_with_errors(shares: &[Bip39Share], t: u8) -> Result<(Bip39Secret, Vec<u8>)> {
+    Bip39Secret::reconstruct_with_errors(shares, t)
+}
+
+/// Check that a mnemonic (of a secret or of a share) is a valid bip-39 mnemonic.
+///
+/// ```
+/// use shamir_bip39::{Bip39Dictionary, Language};
+///
+/// let dictionary = Bip39Dictionary::builtin(Language::English)?;
+/// assert!(shamir_bip39::check("abandon ".repeat(11).trim(), &dictionary).is_err());
+/// assert!(shamir_bip39::check(&format!("{}about", "abandon ".repeat(11)), &dictionary).is_ok());
+/// # Ok::<(), shamir_bip39::Error>(())
+/// ```
+pub fn check(mnemonic: &str, dictionary: &Bip39Dictionary) -> Result<()> {
+    Bip39Secret::from_mnemonic(mnemonic, dictionary)?.is_valid()
+}
@@ -1,38 +1,60 @@
 // Copyright (c) Alberto Sonnino
 // SPDX-License-Identifier: Apache-2.0
 
-mod bip39;
-mod gf256;
-mod shamir;
-mod utils;
+mod input;
+mod memory;
 
-use std::str::FromStr;
+use std::{
+    cell,
+    fmt,
+    fs,
+    io::{self, Write as _},
+    iter::once,
+    path::Path,
+};
 
-use clap::{command, Parser};
+use clap::{command, Parser, ValueEnum};
 use color_eyre::owo_colors::OwoColorize;
-use eyre::{ensure, Result};
-use prettytable::{
-    format::{FormatBuilder, LinePosition, LineSeparator},
-    Cell,
-    Row,
-    Table,
+use eyre::{ensure, Result, WrapErr};
+use rand::{rngs::OsRng, TryRngCore};
+use shamir_bip39::{
+    convert_mnemonic,
+    entropy_bits,
+    Bip39Dictionary,
+    Bip39Secret,
+    Bip39Share,
+    EntropySource,
+    Language,
+    ShamirSecretSharing,
+    MAX_RECOVERY_CANDIDATES,
 };
+use unicode_width::UnicodeWidthStr;
+use zeroize::Zeroizing;
 
-use crate::{
-    bip39::{Bip39Dictionary, Bip39Secret, Bip39Share},
-    shamir::ShamirSecretSharing,
+use crate::input::{
+    read_commitment,
+    read_mnemonic_or_share,
+    read_proofs,
+    read_secret,
+    read_shares,
+    ShareString,
 };
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[clap(
-        long,
-        value_name = "FILE",
-        default_value = "assets/bip39-en.txt",
-        global = true
-    )]
-    dictionary_path: String,
+    /// The language of the embedded bip-39 dictionary (detected from the input if omitted).
+    #[clap(long, value_enum, global = true)]
+    language: Option<LanguageArg>,
+
+    /// Load the bip-39 dictionary from a file instead of using an embedded wordlist.
+    #[clap(long, value_name = "FILE", global = true)]
+    dictionary_path: Option<String>,
+
+    /// Fail if the memory holding secrets cannot be locked in RAM (or excluded from core dumps)
+    /// instead of printing a warning.
+    #[clap(long, global = true)]
+    require_locked_memory: bool,
 
     #[clap(subcommand)]
     operation: Operation,
@@ -42,137 +64,899 @@ struct Args {
 enum Operation {
     /// Split a bip-39 secret into shares.
     Split {
-        /// The bip-39 secret to split.
-        #[clap(short, long, value_name = "STR")]
-        secret: String,
+        /// The bip-39 secret to split (12, 15, 18, 21, or 24 words). Prefer `--secret-file` or
+        /// the interactive prompt (used if neither is provided) to keep it out of the shell
+        /// history.
+        #[clap(short, long, value_name = "STR", conflicts_with = "secret_file")]
+        secret: Option<String>,
+        /// Read the bip-39 secret from a file ('-' for the standard input).
+        #[clap(long, value_name = "FILE")]
+        secret_file: Option<String>,
         /// The number of shares to generate.
         #[clap(short, long, value_name = "INT")]
         n: u8,
         /// The threshold number of shares required to reconstruct the secret.
         #[clap(short, long, value_name = "INT")]
         t: u8,
+        /// Print each word of the shares as its stem (its first four letters) for stamping.
+        #[clap(long)]
+        stems: bool,
+        /// Write a public commitment to the polynomial the shares lie on to this file (and the
+        /// proof of each share to `--proofs-dir`), so that each shareholder can check their share
+        /// with `verify-share`.
+        #[clap(long, value_name = "FILE", requires = "proofs_dir")]
+        commitments_file: Option<String>,
+        /// Write the proof of each share to "share-ID.proof" in this directory. Give each proof
+        /// only to the holder of the share, since it can be used to test guesses of the share.
+        #[clap(long, value_name = "DIR", requires = "commitments_file")]
+        proofs_dir: Option<String>,
+    },
+    /// Generate a new bip-39 secret from the OS random number generator (or from physical
+    /// randomness).
+    Generate {
+        /// The number of words of the secret (12, 15, 18, 21, or 24).
+        #[clap(short, long, value_name = "INT", default_value = "24")]
+        words: usize,
+        /// Derive the secret from physical randomness instead of the OS random number generator.
+        /// The outcomes are read from `--outcomes`, `--outcomes-file`, or else an interactive
+        /// prompt.
+        #[clap(long, value_enum)]
+        entropy_source: Option<EntropySourceArg>,
+        /// The outcomes of the physical source of randomness (e.g., "3 6 1 4 ..." for d6 rolls).
+        /// Prefer `--outcomes-file` or the interactive prompt to keep them out of the shell
+        /// history, since they determine the secret.
+        #[clap(
+            long,
+            value_name = "STR",
+            requires = "entropy_source",
+            conflicts_with = "outcomes_file"
+        )]
+        outcomes: Option<String>,
+        /// Read the outcomes of the physical source of randomness from a file ('-' for the
+        /// standard input).
+        #[clap(long, value_name = "FILE", requires = "entropy_source")]
+        outcomes_file: Option<String>,
+        /// XOR the physical randomness with the OS random number generator.
+        #[clap(long, requires = "entropy_source")]
+        mix: bool,
+        /// Split the secret into this number of shares instead of printing it.
+        #[clap(short, long, value_name = "INT", requires = "t")]
+        n: Option<u8>,
+        /// The threshold number of shares required to reconstruct the secret.
+        #[clap(short, long, value_name = "INT", requires = "n")]
+        t: Option<u8>,
+        /// Print each word of the shares as its stem (its first four letters) for stamping.
+        #[clap(long, requires = "n")]
+        stems: bool,
+        /// Write a public commitment to the polynomial the shares lie on to this file (and the
+        /// proof of each share to `--proofs-dir`), so that each shareholder can check their share
+        /// with `verify-share`.
+        #[clap(long, value_name = "FILE", requires = "n", requires = "proofs_dir")]
+        commitments_file: Option<String>,
+        /// Write the proof of each share to "share-ID.proof" in this directory. Give each proof
+        /// only to the holder of the share, since it can be used to test guesses of the share.
+        #[clap(long, value_name = "DIR", requires = "commitments_file")]
+        proofs_dir: Option<String>,
     },
     /// Reconstruct a bip-39 secret from shares.
     Reconstruct {
         /// Shares are provided in the following format:
         /// "INDEX_I WORD_1 .. WORD_2,INDEX_K WORD_1 .. WORD_2, ..."
-        #[clap(short, long, value_name = "[STR]", value_delimiter = ',', num_args(1..))]
+        /// Prefer `--shares-file` or the interactive prompt (used if neither is provided) to keep
+        /// them out of the shell history.
+        #[clap(
+            short,
+            long,
+            value_name = "[STR]",
+            value_delimiter = ',',
+            num_args(1..),
+            conflicts_with = "shares_file"
+        )]
         shares: Vec<ShareString>,
+        /// Read the shares from a file ('-' for the standard input), one share per line in the
+        /// following format: "INDEX WORD_1 .. WORD_2"
+        #[clap(long, value_name = "FILE")]
+        shares_file: Option<String>,
+        /// The threshold the secret was split with. When provided with more shares than the
+        /// threshold, correct and report up to half of the extra shares if they are corrupted.
+        #[clap(short, long, value_name = "INT")]
+        t: Option<u8>,
+    },
+    /// Check that shares lie on the polynomial committed to when the secret was split.
+    VerifyShare {
+        /// Shares are provided in the following format:
+        /// "INDEX_I WORD_1 .. WORD_2,INDEX_K WORD_1 .. WORD_2, ..."
+        /// Prefer `--shares-file` or the interactive prompt (used if neither is provided) to keep
+        /// them out of the shell history.
+        #[clap(
+            short,
+            long,
+            value_name = "[STR]",
+            value_delimiter = ',',
+            num_args(1..),
+            conflicts_with = "shares_file"
+        )]
+        shares: Vec<ShareString>,
+        /// Read the shares from a file ('-' for the standard input), one share per line in the
+        /// following format: "INDEX WORD_1 .. WORD_2"
+        #[clap(long, value_name = "FILE")]
+        shares_file: Option<String>,
+        /// The commitment file written by `split` or `generate` ('-' for the standard input).
+        #[clap(short, long, value_name = "FILE")]
+        commitments_file: String,
+        /// The proof files of the shares written by `split` or `generate` (in any order).
+        #[clap(
+            short,
+            long,
+            value_name = "[FILE]",
+            value_delimiter = ',',
+            num_args(1..),
+            required = true
+        )]
+        proof_files: Vec<String>,
     },
     /// Ensure a string is a valid bip-39 mnemonic.
     Check {
-        /// The bip-39 mnemonic to check.
-        #[clap(short, long, value_name = "STR")]
-        mnemonic: String,
+        /// The bip-39 mnemonic to check. Prefer `--mnemonic-file` or the interactive prompt (used
+        /// if neither is provided) to keep it out of the shell history.
+        #[clap(short, long, value_name = "STR", conflicts_with = "mnemonic_file")]
+        mnemonic: Option<String>,
+        /// Read the bip-39 mnemonic from a file ('-' for the standard input).
+        #[clap(long, value_name = "FILE")]
+        mnemonic_file: Option<String>,
+        /// Replace misspelled words with their closest dictionary words and list the
+        /// replacements producing a valid checksum.
+        #[clap(long)]
+        correct: bool,
+    },
+    /// Recover up to two missing words of a bip-39 secret or share.
+    RecoverWords {
+        /// The bip-39 mnemonic of the secret, with a '?' in place of each missing word. Missing
+        /// words at unknown positions may be left out. Prefer `--mnemonic-file`, `--share-file`,
+        /// or the interactive prompt (used if no mnemonic or share is provided, and accepting
+        /// either) to keep it out of the shell history.
+        #[clap(
+            short,
+            long,
+            value_name = "STR",
+            conflicts_with_all = ["mnemonic_file", "share", "share_file"]
+        )]
+        mnemonic: Option<String>,
+        /// Read the bip-39 mnemonic of the secret from a file ('-' for the standard input).
+        #[clap(long, value_name = "FILE", conflicts_with_all = ["share", "share_file"])]
+        mnemonic_file: Option<String>,
+        /// The share, provided in the following format: "INDEX WORD_1 .. WORD_2" (with a '?' in
+        /// place of each missing word).
+        #[clap(short, long, value_name = "STR", conflicts_with = "share_file")]
+        share: Option<ShareString>,
+        /// Read the share from a file ('-' for the standard input).
+        #[clap(long, value_name = "FILE")]
+        share_file: Option<String>,
+        /// Other shares of the same secret, used to cross-check the candidates of the share.
+        /// Shares are provided in the following format:
+        /// "INDEX_I WORD_1 .. WORD_2,INDEX_K WORD_1 .. WORD_2, ..."
+        /// Prefer `--other-shares-file` or the interactive prompt (used if neither is provided
+        /// with `-t`) to keep them out of the shell history.
+        #[clap(
+            long,
+            value_name = "[STR]",
+            value_delimiter = ',',
+            num_args(1..),
+            requires = "t",
+            conflicts_with = "other_shares_file"
+        )]
+        other_shares: Vec<ShareString>,
+        /// Read the other shares from a file ('-' for the standard input), one share per line in
+        /// the following format: "INDEX WORD_1 .. WORD_2"
+        #[clap(long, value_name = "FILE", requires = "t")]
+        other_shares_file: Option<String>,
+        /// The threshold number of shares required to reconstruct the secret, to cross-check
+        /// the candidates of the share with the other shares.
+        #[clap(short, long, value_name = "INT")]
+        t: Option<u8>,
+        /// Fail instead of searching more than this number of candidate mnemonics (by default,
+        /// enough for any two missing words).
+        #[clap(long, value_name = "INT")]
+        max_candidates: Option<usize>,
+    },
+    /// Translate a bip-39 secret or share into the wordlist of another language.
+    Convert {
+        /// The bip-39 mnemonic of the secret to convert. Prefer `--mnemonic-file`, `--share-file`,
+        /// or the interactive prompt (used if no mnemonic or share is provided, and accepting
+        /// either) to keep it out of the shell history.
+        #[clap(
+            short,
+            long,
+            value_name = "STR",
+            conflicts_with_all = ["mnemonic_file", "share", "share_file"]
+        )]
+        mnemonic: Option<String>,
+        /// Read the bip-39 mnemonic of the secret from a file ('-' for the standard input).
+        #[clap(long, value_name = "FILE", conflicts_with_all = ["share", "share_file"])]
+        mnemonic_file: Option<String>,
+        /// The share to convert, provided in the following format: "INDEX WORD_1 .. WORD_2"
+        #[clap(short, long, value_name = "STR", conflicts_with = "share_file")]
+        share: Option<ShareString>,
+        /// Read the share to convert from a file ('-' for the standard input).
+        #[clap(long, value_name = "FILE")]
+        share_file: Option<String>,
+        /// The language of the converted mnemonic.
+        #[clap(long, value_enum)]
+        to: LanguageArg,
     },
 }
 
-#[derive(Clone)]
-struct ShareString {
-    index: u8,
-    secret: String,
+/// The languages of the embedded bip-39 dictionaries, as command-line values.
+#[derive(Clone, Copy, ValueEnum)]
+enum LanguageArg {
+    English,
+    Japanese,
+    Korean,
+    Spanish,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Czech,
+    Portuguese,
 }
 
-impl FromStr for ShareString {
-    type Err = std::num::ParseIntError;
+impl From<LanguageArg> for Language {
+    fn from(language: LanguageArg) -> Self {
+        match language {
+            LanguageArg::English => Self::English,
+            LanguageArg::Japanese => Self::Japanese,
+            LanguageArg::Korean => Self::Korean,
+            LanguageArg::Spanish => Self::Spanish,
+            LanguageArg::ChineseSimplified => Self::ChineseSimplified,
+            LanguageArg::ChineseTraditional => Self::ChineseTraditional,
+            LanguageArg::French => Self::French,
+            LanguageArg::Italian => Self::Italian,
+            LanguageArg::Czech => Self::Czech,
+            LanguageArg::Portuguese => Self::Portuguese,
+        }
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(' ');
-        let index = parts.next().unwrap().parse()?;
-        let secret = parts.collect::<Vec<_>>().join(" ");
-        Ok(Self { index, secret })
+/// The physical sources of randomness, as command-line values.
+#[derive(Clone, Copy, ValueEnum)]
+enum EntropySourceArg {
+    /// Coin flips, written as 'H' or 'T' (or '1' or '0').
+    Coin,
+    /// Rolls of a six-sided die, written as digits from 1 to 6.
+    D6,
+    /// Rolls of a twenty-sided die, written as numbers from 1 to 20 separated by spaces.
+    D20,
+    /// Shuffled decks of 52 cards, written as rank and suit separated by spaces (e.g., "AS 10H").
+    Cards,
+}
+
+impl From<EntropySourceArg> for EntropySource {
+    fn from(source: EntropySourceArg) -> Self {
+        match source {
+            EntropySourceArg::Coin => Self::Coin,
+            EntropySourceArg::D6 => Self::D6,
+            EntropySourceArg::D20 => Self::D20,
+            EntropySourceArg::Cards => Self::Cards,
+        }
     }
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Args::parse();
+    let dictionary_path = args.dictionary_path.as_deref();
+    let language = args.language.map(Language::from);
 
-    // Load the bip-39 dictionary.
-    let dictionary = Bip39Dictionary::load(&args.dictionary_path)?;
+    // Prevent the secrets from being written to disk by a crash or swapped out of memory.
+    let locker = MemoryLocker::new(args.require_locked_memory);
+    locker.check(disable_core_dumps())?;
 
     match args.operation {
-        Operation::Split { secret, n, t } => {
-            ensure!(n > 0, "There must be at least one share");
-            ensure!(t > 0, "The threshold must be at least one");
-            ensure!(t <= n, "The threshold must be lower than the total shares");
+        Operation::Split {
+            secret,
+            secret_file,
+            n,
+            t,
+            stems,
+            commitments_file,
+            proofs_dir,
+        } => {
+            // Read the secret from the command line, a file, or the prompt.
+            let secret = read_secret(secret, secret_file.as_deref(), "secret mnemonic")?;
+            locker.lock(secret.as_bytes())?;
+
+            // Load the bip-39 dictionary.
+            let dictionary = load_dictionary(dictionary_path, language, [secret.as_str()])?;
 
             // Generate a bip-39 secret from the input mnemonic.
             let secret = Bip39Secret::from_mnemonic(&secret, &dictionary)?;
+            locker.lock_secret(&secret)?;
 
             // Ensure the secret is valid with respect to the bip-39 standard.
             secret.is_valid()?;
-            // Split the secret into the specified number of shares.
-            let shares = secret.split(n, t, &mut rand::rng());
+            // Split the secret and print the shares to stdout.
+            let paths = commitments_file.as_deref().zip(proofs_dir.as_deref());
+            split_secret(&secret, n, t, stems, paths, &dictionary, &locker)?;
+        }
+        Operation::Generate {
+            words,
+            entropy_source,
+            outcomes,
+            outcomes_file,
+            mix,
+            n,
+            t,
+            stems,
+            commitments_file,
+            proofs_dir,
+        } => {
+            // Load the bip-39 dictionary (English unless specified otherwise).
+            let language = language.or(Some(Language::English));
+            let dictionary = load_dictionary(dictionary_path, language, [])?;
 
-            // Print the shares to stdout.
-            for (i, share) in shares.iter().enumerate() {
-                let heading = format!("Share {}/{}", i + 1, n);
-                pretty_print_mnemonic(&heading, &share.to_mnemonic(&dictionary));
-            }
-            println!("The secret can be reconstructed from any {t} out of {n} shares");
+            // Generate a bip-39 secret from the OS random number generator, or from the bits
+            // extracted from the physical outcomes (optionally mixed with the OS randomness).
+            let secret = match entropy_source.map(EntropySource::from) {
+                Some(source) => {
+                    // Read the outcomes from the command line, a file, or the prompt.
+                    let outcomes = read_secret(outcomes, outcomes_file.as_deref(), "outcomes")?;
+                    locker.lock(outcomes.as_bytes())?;
+                    let bits = source.collect_bits(&outcomes)?;
+                    locker.lock(&bits)?;
+                    eprintln!(
+                        "Collected {} bits of entropy from the {source:?} outcomes ({} needed)",
+                        bits.len(),
+                        entropy_bits(words)
+                    );
+                    let secret = Bip39Secret::from_entropy_bits(&bits, words)?;
+                    if mix {
+                        secret.mix(&mut OsRng.unwrap_err())
+                    } else {
+                        secret
+                    }
+                }
+                None => Bip39Secret::generate(words, &mut OsRng.unwrap_err())?,
+            };
+            locker.lock_secret(&secret)?;
 
-            // Double-check that the secret can be reconstructed from the shares.
-            #[cfg(feature = "double-check")]
-            double_check_shares(&secret, &shares, t as usize, &dictionary)?;
+            // Split the secret without printing it, or print it if no shares are requested.
+            match (n, t) {
+                (Some(n), Some(t)) => {
+                    let paths = commitments_file.as_deref().zip(proofs_dir.as_deref());
+                    split_secret(&secret, n, t, stems, paths, &dictionary, &locker)?
+                }
+                _ => {
+                    let mnemonic = Zeroizing::new(secret.to_mnemonic(&dictionary));
+                    locker.lock(mnemonic.as_bytes())?;
+                    pretty_print_mnemonic("Master Secret", &mnemonic)?;
+                }
+            }
         }
-        Operation::Reconstruct { shares } => {
+        Operation::Reconstruct {
+            shares,
+            shares_file,
+            t,
+        } => {
+            // Read the shares from the command line, a file, or the prompt.
+            let shares = read_shares(shares, shares_file.as_deref())?;
+            for share in &shares {
+                locker.lock(share.secret.as_bytes())?;
+            }
+
+            // Load the bip-39 dictionary (all shares must use the same wordlist).
+            let mnemonics = shares.iter().map(|share| share.secret.as_str());
+            let dictionary = load_dictionary(dictionary_path, language, mnemonics)?;
+
             // Generate a bip-39 share from each input mnemonic.
             let shares = shares
                 .into_iter()
                 .map(|share| Bip39Share::from_mnemonic(share.index, &share.secret, &dictionary))
-                .collect::<Result<Vec<_>>>()?;
+                .collect::<Result<Vec<_>, _>>()?;
 
-            // Ensure each share is valid with respect to the bip-39 standard.
             for share in &shares {
-                share.is_valid()?;
+                locker.lock_secret(share.secret())?;
             }
 
-            // Reconstruct the master secret from the shares.
-            let secret = Bip39Secret::reconstruct(&shares);
+            // Reconstruct the master secret from the shares, correcting corrupted shares if the
+            // threshold is known (or else ensuring they are valid with respect to the bip-39
+            // standard).
+            let secret = match t {
+                Some(t) => {
+                    let (secret, corrupted) = shamir_bip39::reconstruct_with_errors(&shares, t)
+                        .wrap_err("Failed to reconstruct the secret from the shares")?;
+                    print_corrupted_shares(&corrupted, shares.len(), t);
+                    secret
+                }
+                None => shamir_bip39::reconstruct(&shares)
+                    .wrap_err("Failed to reconstruct the secret from the shares")?,
+            };
+            locker.lock_secret(&secret)?;
 
             // Print the master secret to stdout.
-            pretty_print_mnemonic("Master Secret", &secret.to_mnemonic(&dictionary));
+            let mnemonic = Zeroizing::new(secret.to_mnemonic(&dictionary));
+            locker.lock(mnemonic.as_bytes())?;
+            pretty_print_mnemonic("Master Secret", &mnemonic)?;
         }
-        Operation::Check { mnemonic } => {
+        Operation::VerifyShare {
+            shares,
+            shares_file,
+            commitments_file,
+            proof_files,
+        } => {
+            // Read the commitment, the proofs, and the shares (from the command line, a file, or
+            // the prompt).
+            let commitment = read_commitment(&commitments_file)?;
+            let proofs = read_proofs(&proof_files)?;
+            let shares = read_shares(shares, shares_file.as_deref())?;
+            for share in &shares {
+                locker.lock(share.secret.as_bytes())?;
+            }
+
+            // Load the bip-39 dictionary (all shares must use the same wordlist).
+            let mnemonics = shares.iter().map(|share| share.secret.as_str());
+            let dictionary = load_dictionary(dictionary_path, language, mnemonics)?;
+
+            // Check that the commitment is to a polynomial, then each share against it with the
+            // proof with its ID.
+            commitment
+                .verify()
+                .wrap_err_with(|| format!("Invalid commitment in {commitments_file}"))?;
+            let mut mismatches = 0;
+            for share in &shares {
+                let share = Bip39Share::from_mnemonic(share.index, &share.secret, &dictionary)?;
+                locker.lock_secret(share.secret())?;
+                match shamir_bip39::verify_share(&share, &commitment, &proofs) {
+                    Ok(()) => println!(
+                        "{} The share with ID {} lies on the committed polynomial (threshold {})",
+                        "OK:".green().bold(),
+                        share.id(),
+                        commitment.threshold()
+                    ),
+                    Err(e) => {
+                        mismatches += 1;
+                        println!("{} {e}", "MISMATCH:".red().bold());
+                    }
+                }
+            }
+            ensure!(
+                mismatches == 0,
+                "{mismatches} of {} shares do not match the commitment",
+                shares.len()
+            );
+        }
+        Operation::Check {
+            mnemonic,
+            mnemonic_file,
+            correct,
+        } => {
+            // Read the mnemonic from the command line, a file, or the prompt.
+            let mnemonic = read_secret(mnemonic, mnemonic_file.as_deref(), "mnemonic")?;
+            locker.lock(mnemonic.as_bytes())?;
+
+            // Load the bip-39 dictionary.
+            let dictionary = load_dictionary(dictionary_path, language, [mnemonic.as_str()])?;
+
+            // List the corrections of the misspelled words producing a valid mnemonic.
+            if correct {
+                let corrections = Zeroizing::new(Bip39Secret::corrections(&mnemonic, &dictionary)?);
+                if corrections.is_empty() {
+                    println!("\n{}\n", "No valid correction found".red().bold());
+                }
+                for (i, correction) in corrections.iter().enumerate() {
+                    let heading = format!("Candidate {}/{}", i + 1, corrections.len());
+                    pretty_print_mnemonic(&heading, correction)?;
+                }
+                return Ok(());
+            }
+
             // Ensure the mnemonic is valid with respect to the bip-39 standard.
-            match Bip39Secret::from_mnemonic(&mnemonic, &dictionary)?.is_valid() {
+            match shamir_bip39::check(&mnemonic, &dictionary) {
                 Ok(()) => println!("\n{}\n", "The mnemonic is valid".green()),
                 Err(e) => println!("\n{} {e}\n", "Invalid mnemonic:".red().bold()),
             }
         }
+        Operation::RecoverWords {
+            mnemonic,
+            mnemonic_file,
+            share,
+            share_file,
+            other_shares,
+            other_shares_file,
+            t,
+            max_candidates,
+        } => {
+            // Read the mnemonic (or share) and the other shares from the command line, a file, or
+            // the prompt.
+            let (id, mnemonic) = read_mnemonic_or_share(
+                mnemonic,
+                mnemonic_file.as_deref(),
+                share,
+                share_file.as_deref(),
+            )?;
+            locker.lock(mnemonic.as_bytes())?;
+            let other_shares = match t {
+                Some(_) => {
+                    ensure!(
+                        id.is_some(),
+                        "Other shares can only cross-check the candidates of a share"
+                    );
+                    read_shares(other_shares, other_shares_file.as_deref())?
+                }
+                None => Vec::new(),
+            };
+            for share in &other_shares {
+                locker.lock(share.secret.as_bytes())?;
+            }
+
+            // Load the bip-39 dictionary (the placeholders are not words of any dictionary).
+            let mnemonics = Zeroizing::new(
+                once(mnemonic.as_str())
+                    .chain(other_shares.iter().map(|share| share.secret.as_str()))
+                    .map(|m| {
+                        let words = m.split_whitespace().filter(|w| *w != "?");
+                        words.collect::<Vec<_>>().join(" ")
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            let dictionary = load_dictionary(
+                dictionary_path,
+                language,
+                mnemonics.iter().map(String::as_str),
+            )?;
+
+            // Generate a bip-39 share from each of the other mnemonics.
+            let others = other_shares
+                .into_iter()
+                .map(|share| Bip39Share::from_mnemonic(share.index, &share.secret, &dictionary))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Try all dictionary words in place of the missing words, and print the candidates to
+            // stdout as soon as they are found (cross-checked with the other shares if any).
+            let mut found = 0;
+            let mut searched = 0;
+            Bip39Secret::search_missing_words(
+                &mnemonic,
+                &dictionary,
+                max_candidates.unwrap_or(MAX_RECOVERY_CANDIDATES),
+                |candidate| {
+                    found += 1;
+                    let mut heading = format!("Candidate {found}");
+                    let mnemonic = Zeroizing::new(candidate.to_mnemonic(&dictionary));
+                    if let (Some(id), Some(t), false) = (id, t, others.is_empty()) {
+                        let consistent =
+                            Bip39Share::new(id, candidate).is_consistent(&others, t)?;
+                        let status = if consistent {
+                            "consistent"
+                        } else {
+                            "inconsistent"
+                        };
+                        heading = format!("{heading} ({status} with the other shares)");
+                    }
+                    pretty_print_mnemonic(&heading, &mnemonic)?;
+                    Ok(())
+                },
+                |tried, total| {
+                    let percent = tried * 100 / total;
+                    if percent > searched {
+                        searched = percent;
+                        eprint!("\rSearched {percent}% of {total} candidate mnemonics");
+                    }
+                },
+            )?;
+            eprintln!();
+
+            if found == 0 {
+                println!("\n{}\n", "No valid candidate found".red().bold());
+            } else {
+                println!("Found {found} candidates with a valid checksum");
+            }
+        }
+        Operation::Convert {
+            mnemonic,
+            mnemonic_file,
+            share,
+            share_file,
+            to,
+        } => {
+            // Read the mnemonic (or share) from the command line, a file, or the prompt.
+            let (id, mnemonic) = read_mnemonic_or_share(
+                mnemonic,
+                mnemonic_file.as_deref(),
+                share,
+                share_file.as_deref(),
+            )?;
+            locker.lock(mnemonic.as_bytes())?;
+            let heading = match id {
+                Some(id) => format!("Share {id}"),
+                None => "Master Secret".to_string(),
+            };
+
+            // Load the bip-39 dictionaries of the input and of the output.
+            let dictionary = load_dictionary(dictionary_path, language, [mnemonic.as_str()])?;
+            let to = Language::from(to);
+            let target = Bip39Dictionary::builtin(to)?;
+
+            // Re-encode the mnemonic (and ensure it is valid with respect to the bip-39 standard).
+            let converted = Zeroizing::new(convert_mnemonic(&mnemonic, &dictionary, &target)?);
+
+            // Print the converted mnemonic to stdout.
+            pretty_print_mnemonic(&format!("{heading} ({to:?})"), &converted)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Split the secret into `n` shares with threshold `t` and print the shares to stdout. If the
+/// paths of a commitment file and of a proofs directory are provided, also commit to the
+/// polynomial and write the commitment and the proof of each share.
+fn split_secret(
+    secret: &Bip39Secret,
+    n: u8,
+    t: u8,
+    stems: bool,
+    commitment_paths: Option<(&str, &str)>,
+    dictionary: &Bip39Dictionary,
+    locker: &MemoryLocker,
+) -> Result<()> {
+    // Reject stems before splitting if they do not identify the words of the dictionary.
+    ensure!(
+        !stems || dictionary.has_unique_stems(),
+        "The words of the dictionary are not identified by their first four letters, so the \
+        shares cannot be printed as stems"
+    );
+
+    // Split the secret into the specified number of shares (committing to the polynomial).
+    let (shares, verifiable) = match commitment_paths {
+        Some(_) => {
+            let (shares, commitment, proofs) = secret.split_verifiable(n, t, &mut rand::rng())?;
+            (shares, Some((commitment, proofs)))
+        }
+        None => (secret.split(n, t, &mut rand::rng())?, None),
+    };
+    if t == 1 {
+        eprintln!(
+            "{} {}",
+            "WARNING:".red().bold(),
+            "With a threshold of 1, every share is a copy of the secret and the sharing offers no \
+            protection."
+                .red()
+        );
     }
 
+    // Encode all the shares before writing or printing anything, so that a failure leaves no
+    // partial output.
+    let mnemonics = shares
+        .iter()
+        .map(|share| {
+            locker.lock_secret(share.secret())?;
+            let mnemonic = Zeroizing::new(if stems {
+                share.to_stems(dictionary)?
+            } else {
+                share.to_mnemonic(dictionary)
+            });
+            locker.lock(mnemonic.as_bytes())?;
+            Ok(mnemonic)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Write the commitment, to be published to the shareholders, and the proof of each share, to
+    // be given to its holder only. They are written before the shares are printed, so that no
+    // share is handed out if they cannot be.
+    if let (Some((path, directory)), Some((commitment, proofs))) = (commitment_paths, &verifiable) {
+        fs::write(path, commitment.to_string())
+            .wrap_err_with(|| format!("Failed to write the commitment to {path}"))?;
+        fs::create_dir_all(directory)
+            .wrap_err_with(|| format!("Failed to create the directory {directory}"))?;
+        for proof in proofs {
+            let path = Path::new(directory).join(format!("share-{}.proof", proof.id()));
+            fs::write(&path, proof.to_string())
+                .wrap_err_with(|| format!("Failed to write the proof to {}", path.display()))?;
+        }
+    }
+
+    // Print the shares to stdout.
+    for (i, mnemonic) in mnemonics.iter().enumerate() {
+        let heading = format!("Share {}/{}", i + 1, n);
+        pretty_print_mnemonic(&heading, mnemonic)?;
+    }
+    println!("The secret can be reconstructed from any {t} out of {n} shares");
+    if let Some((path, directory)) = commitment_paths {
+        println!("The commitment to the polynomial is written to {path}");
+        println!("The proof of each share is written to {directory}, give it to its holder only");
+    }
+
+    // Double-check that the secret can be reconstructed from the shares.
+    #[cfg(feature = "double-check")]
+    double_check_shares(secret, &shares, t as usize, dictionary)?;
     Ok(())
 }
 
+/// Report the corrupted shares found while reconstructing a secret from `n` shares with threshold
+/// `t`, or else how many corrupted shares would have been detected.
+fn print_corrupted_shares(corrupted: &[u8], n: usize, t: u8) {
+    if !corrupted.is_empty() {
+        let ids = corrupted.iter().map(u8::to_string).collect::<Vec<_>>();
+        eprintln!(
+            "{} {}",
+            "WARNING:".red().bold(),
+            format!(
+                "Ignored the corrupted shares (disagreeing with the others) with IDs: {}",
+                ids.join(", ")
+            )
+            .red()
+        );
+    } else if n == t as usize {
+        eprintln!(
+            "{} Provide more than {t} shares to detect corrupted shares",
+            "Warning:".yellow().bold()
+        );
+    } else {
+        let extra = n - t as usize;
+        eprintln!(
+            "All {n} shares are consistent (up to {} corrupted shares are corrected, and up to {} \
+            detected)",
+            extra / 2,
+            extra - extra / 2
+        );
+    }
+}
+
+/// Disable core dumps and mark the process as non-dumpable (which also prevents other processes of
+/// the user from attaching to it or reading its memory) for the lifetime of the process, so that a
+/// crash cannot write secrets to disk.
+#[cfg(target_os = "linux")]
+fn disable_core_dumps() -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: The limit is a valid `rlimit` structure that outlives the call.
+    if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `PR_SET_DUMPABLE` only reads its (integer) second argument.
+    if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn disable_core_dumps() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Disabling core dumps is only supported on Linux",
+    ))
+}
+
+/// Locks the memory holding secrets in RAM, and fails or warns (once) if it is not permitted.
+struct MemoryLocker {
+    /// Whether to fail if the memory cannot be protected.
+    required: bool,
+    /// Whether a warning has already been printed.
+    warned: cell::Cell<bool>,
+}
+
+impl MemoryLocker {
+    fn new(required: bool) -> Self {
+        Self {
+            required,
+            warned: cell::Cell::new(false),
+        }
+    }
+
+    /// Lock the memory holding a buffer.
+    fn lock<T>(&self, buffer: &[T]) -> Result<()> {
+        self.check(memory::lock(buffer))
+    }
+
+    /// Lock the memory holding a bip-39 secret.
+    fn lock_secret(&self, secret: &Bip39Secret) -> Result<()> {
+        self.check(secret.lock_memory())
+    }
+
+    /// Fail if the memory protection failed and is required, or else print a warning.
+    fn check(&self, result: io::Result<()>) -> Result<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if self.required => {
+                Err(e).wrap_err("Failed to protect the memory holding the secrets")
+            }
+            Err(e) => {
+                if !self.warned.replace(true) {
+                    eprintln!(
+                        "{} Failed to protect the memory holding the secrets ({e}), they may be \
+                        written to swap or to a core dump; use --require-locked-memory to abort \
+                        instead",
+                        "Warning:".yellow().bold()
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Load the bip-39 dictionary from a file if a path is provided, or else the embedded wordlist of
+/// the given language. If neither is provided, use the embedded wordlist containing all the words
+/// of the mnemonics.
+fn load_dictionary<'a, I: IntoIterator<Item = &'a str>>(
+    dictionary_path: Option<&str>,
+    language: Option<Language>,
+    mnemonics: I,
+) -> Result<Bip39Dictionary> {
+    match (dictionary_path, language) {
+        (Some(path), _) => {
+            let dictionary = Bip39Dictionary::load(path)?;
+            eprintln!(
+                "Loaded dictionary {path} (SHA-256: {})",
+                dictionary.digest()
+            );
+            for warning in dictionary.warnings() {
+                eprintln!("{} {warning}", "Warning:".yellow().bold());
+            }
+            Ok(dictionary)
+        }
+        (None, Some(language)) => Ok(Bip39Dictionary::builtin(language)?),
+        (None, None) => Ok(Bip39Dictionary::builtin(Language::detect(mnemonics)?)?),
+    }
+}
+
+/// The number of words in each column of the printed mnemonics.
+const TABLE_ROWS: usize = 4;
+
 /// Pretty-print a bip-39 mnemonic.
-fn pretty_print_mnemonic(heading: &str, mnemonic: &str) {
-    let words = mnemonic
-        .split_whitespace()
-        .enumerate()
-        .map(|(j, word)| format!("{:2} {}", (j + 1).to_string().bold(), word))
-        .collect::<Vec<_>>();
-
-    let chunks = words
-        .chunks(4)
-        .map(|chunk| Cell::new(&chunk.join("\n")))
-        .collect::<Vec<_>>();
-
-    let mut table = Table::new();
-    let format = FormatBuilder::new()
-        .separators(
-            &[LinePosition::Top, LinePosition::Bottom, LinePosition::Title],
-            LineSeparator::new('-', '-', '-', '-'),
-        )
-        .padding(1, 1)
-        .build();
-    table.set_format(format);
-    table.add_row(Row::new(chunks));
+fn pretty_print_mnemonic(heading: &str, mnemonic: &str) -> io::Result<()> {
+    // Measure the table first so that it is written into a buffer that never grows (which would
+    // leave an unwiped copy of the words on the heap).
+    let mut length = ByteCounter(0);
+    write_mnemonic_table(&mut length, mnemonic).expect("Counting bytes cannot fail");
+    let mut table = Zeroizing::new(String::with_capacity(length.0));
+    write_mnemonic_table(&mut *table, mnemonic).expect("The buffer is large enough");
 
     println!("\n{}", heading.bold().green());
-    table.printstd();
+    // The table ends with a newline, so it is written to stdout without being buffered.
+    io::stdout().lock().write_all(table.as_bytes())?;
     println!();
+    Ok(())
+}
+
+/// Write the words of a mnemonic as a table of numbered words, in columns of `TABLE_ROWS` words.
+fn write_mnemonic_table(out: &mut impl fmt::Write, mnemonic: &str) -> fmt::Result {
+    let words = || mnemonic.split_whitespace();
+    let columns = (words().count() + TABLE_ROWS - 1) / TABLE_ROWS;
+    let widths = Zeroizing::new(
+        (0..columns)
+            .map(|column| {
+                let chunk = words().skip(column * TABLE_ROWS).take(TABLE_ROWS);
+                chunk.map(UnicodeWidthStr::width).max().unwrap_or(0)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    // Each cell holds a two-digit index and a word, with one space of padding on each side.
+    let rule = widths.iter().map(|width| width + 5).sum::<usize>();
+    writeln!(out, "{:-<rule$}", "")?;
+    for row in 0..TABLE_ROWS {
+        for (j, word) in words().enumerate().skip(row).step_by(TABLE_ROWS) {
+            let padding = widths[j / TABLE_ROWS] - word.width() + 1;
+            write!(out, " {:<2} {word}{:padding$}", (j + 1).bold(), "")?;
+        }
+        writeln!(out)?;
+    }
+    writeln!(out, "{:-<rule$}", "")
+}
+
+/// A writer counting the bytes written to it.
+struct ByteCounter(usize);
+
+impl fmt::Write for ByteCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
 }
 
 /// Double-check that the secret can be reconstructed from any `t` shares.
@@ -221,7 +1005,8 @@ fn double_check_shares(
             .map(|i| &shares[i])
             .collect::<Vec<_>>();
 
-        let reconstructed = Bip39Secret::reconstruct(&shares_subset);
+        let reconstructed = Bip39Secret::reconstruct(&shares_subset)
+            .expect("The secret could not be reconstructed from the shares");
         assert!(
             secret == &reconstructed,
             "The secret could not be reconstructed from the shares"
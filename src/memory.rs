@@ -0,0 +1,40 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io;
+
+/// Lock the pages holding a buffer in RAM so that they are never written to swap. The pages stay
+/// locked until the process exits (unlocking them could unlock other secrets sharing a page).
+#[cfg(target_os = "linux")]
+pub fn lock<T>(buffer: &[T]) -> io::Result<()> {
+    if buffer.is_empty() {
+        return Ok(());
+    }
+    let length = std::mem::size_of_val(buffer);
+    // SAFETY: The address range is that of a valid buffer; mlock rounds it to whole pages.
+    if unsafe { libc::mlock(buffer.as_ptr().cast(), length) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn lock<T>(_buffer: &[T]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Locking memory is only supported on Linux",
+    ))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::lock;
+
+    #[test]
+    fn test_lock() {
+        // The default limit of locked memory (at least 64 KiB) allows locking a few pages.
+        let buffer = vec![true; 1024];
+        lock(&buffer).unwrap();
+        lock::<u8>(&[]).unwrap();
+    }
+}
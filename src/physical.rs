@@ -0,0 +1,203 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use zeroize::Zeroizing;
+
+use crate::error::{Error, Result};
+
+/// The number of cards of a standard deck.
+const DECK_CARDS: usize = 52;
+/// The ranks and suits of the cards, in the order used to index them.
+const CARD_RANKS: [&str; 13] = [
+    "A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K",
+];
+const CARD_SUITS: [&str; 4] = ["C", "D", "H", "S"];
+/// The most bits yielded by a single outcome (a card drawn from a full deck).
+const MAX_OUTCOME_BITS: usize = 5;
+
+/// A physical source of randomness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntropySource {
+    /// Coin flips, written as 'H' or 'T' (or '1' or '0').
+    Coin,
+    /// Rolls of a six-sided die, written as digits from 1 to 6.
+    D6,
+    /// Rolls of a twenty-sided die, written as numbers from 1 to 20 separated by spaces.
+    D20,
+    /// Shuffled decks of 52 cards, written as rank and suit separated by spaces (e.g., "AS 10H").
+    Cards,
+}
+
+impl EntropySource {
+    /// Extract uniformly random bits from the outcomes of the source. Outcomes that would bias
+    /// the bits are rejected, so the number of bits depends on the outcomes.
+    pub fn collect_bits(&self, outcomes: &str) -> Result<Zeroizing<Vec<bool>>> {
+        // Every outcome takes at least one byte, so reserving room for the most bits an outcome
+        // may yield means the bits are never moved (leaving an unwiped copy) as they grow.
+        let mut bits = Zeroizing::new(Vec::with_capacity(MAX_OUTCOME_BITS * outcomes.len()));
+        match self {
+            Self::Coin => {
+                for (i, flip) in outcomes.chars().filter(|c| !c.is_whitespace()).enumerate() {
+                    match flip.to_ascii_uppercase() {
+                        'H' | '1' => bits.push(true),
+                        'T' | '0' => bits.push(false),
+                        _ => return Err(self.invalid_outcome(i, &flip.to_string())),
+                    }
+                }
+            }
+            Self::D6 => {
+                for (i, roll) in outcomes.chars().filter(|c| !c.is_whitespace()).enumerate() {
+                    let value = roll
+                        .to_digit(10)
+                        .filter(|value| (1..=6).contains(value))
+                        .ok_or_else(|| self.invalid_outcome(i, &roll.to_string()))?;
+                    extract_bits(value as usize - 1, 6, &mut bits);
+                }
+            }
+            Self::D20 => {
+                for (i, roll) in outcomes.split_whitespace().enumerate() {
+                    let value = roll
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|value| (1..=20).contains(value))
+                        .ok_or_else(|| self.invalid_outcome(i, roll))?;
+                    extract_bits(value - 1, 20, &mut bits);
+                }
+            }
+            Self::Cards => {
+                // Each card is drawn uniformly from the cards remaining in its deck.
+                let mut remaining = Zeroizing::new(Vec::with_capacity(DECK_CARDS));
+                for (i, card) in outcomes.split_whitespace().enumerate() {
+                    if i % DECK_CARDS == 0 {
+                        remaining.clear();
+                        remaining.extend(0..DECK_CARDS);
+                    }
+                    let index = card_index(card).ok_or_else(|| self.invalid_outcome(i, card))?;
+                    let position = remaining.iter().position(|c| *c == index).ok_or_else(|| {
+                        Error::DuplicateCard {
+                            deck: i / DECK_CARDS,
+                            card: card.to_string(),
+                        }
+                    })?;
+                    extract_bits(position, remaining.len(), &mut bits);
+                    remaining.remove(position);
+                }
+            }
+        }
+        Ok(bits)
+    }
+
+    /// The error for the invalid outcome at the given (zero-based) index.
+    fn invalid_outcome(&self, index: usize, outcome: &str) -> Error {
+        Error::InvalidOutcome {
+            source: *self,
+            index,
+            outcome: outcome.to_string(),
+        }
+    }
+}
+
+/// Extract unbiased bits from a value drawn uniformly from `0..range` by rejection sampling. The
+/// value is kept if it falls within the largest power of two below `range` (and then yields as
+/// many bits as the exponent); otherwise it is rejected and the remaining range is sampled the
+/// same way, so that a d6 roll yields two bits from 1 to 4 and one bit from 5 to 6.
+fn extract_bits(mut value: usize, mut range: usize, bits: &mut Vec<bool>) {
+    while range > 1 {
+        let exponent = range.ilog2();
+        let block = 1 << exponent;
+        if value < block {
+            bits.extend((0..exponent).rev().map(|i| (value >> i) & 1 == 1));
+            return;
+        }
+        value -= block;
+        range -= block;
+    }
+}
+
+/// The index of a card (written as rank and suit, e.g., "AS" or "10H") in a standard deck.
+fn card_index(card: &str) -> Option<usize> {
+    let (last, _) = card.char_indices().last()?;
+    let (rank, suit) = card.split_at(last);
+    let rank = match rank {
+        "T" | "t" => 9,
+        _ => CARD_RANKS
+            .iter()
+            .position(|r| r.eq_ignore_ascii_case(rank))?,
+    };
+    let suit = CARD_SUITS
+        .iter()
+        .position(|s| s.eq_ignore_ascii_case(suit))?;
+    Some(suit * CARD_RANKS.len() + rank)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    use super::{card_index, extract_bits, EntropySource, CARD_RANKS, CARD_SUITS, DECK_CARDS};
+
+    #[test]
+    fn test_extract_bits() {
+        // Every pattern of bits must be equally likely for each number of bits.
+        for range in 2..=DECK_CARDS {
+            let mut patterns = std::collections::HashMap::<_, usize>::new();
+            for value in 0..range {
+                let mut bits = Vec::new();
+                extract_bits(value, range, &mut bits);
+                *patterns.entry(bits).or_default() += 1;
+            }
+            for (bits, count) in &patterns {
+                let same_length = patterns.keys().filter(|b| b.len() == bits.len()).count();
+                assert_eq!(same_length, 1 << bits.len());
+                assert_eq!(*count, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_coin() {
+        let bits = EntropySource::Coin.collect_bits("HT ht 10").unwrap();
+        assert_eq!(*bits, [true, false, true, false, true, false]);
+        assert!(EntropySource::Coin.collect_bits("HTX").is_err());
+    }
+
+    #[test]
+    fn test_d6() {
+        let bits = EntropySource::D6.collect_bits("1 4 5 6").unwrap();
+        assert_eq!(*bits, [false, false, true, true, false, true]);
+        assert!(EntropySource::D6.collect_bits("1 7").is_err());
+        assert!(EntropySource::D6.collect_bits("0").is_err());
+    }
+
+    #[test]
+    fn test_d20() {
+        let bits = EntropySource::D20.collect_bits("1 16 17 20").unwrap();
+        let expected = [
+            [false, false, false, false].as_slice(),
+            &[true, true, true, true],
+            &[false, false],
+            &[true, true],
+        ]
+        .concat();
+        assert_eq!(*bits, expected);
+        assert!(EntropySource::D20.collect_bits("21").is_err());
+    }
+
+    #[test]
+    fn test_cards() {
+        let mut deck = CARD_SUITS
+            .iter()
+            .flat_map(|suit| CARD_RANKS.iter().map(move |rank| format!("{rank}{suit}")))
+            .collect::<Vec<_>>();
+        deck.shuffle(&mut StdRng::from_seed([0; 32]));
+        let bits = EntropySource::Cards.collect_bits(&deck.join(" ")).unwrap();
+        assert!(bits.len() > 128);
+
+        // Cards may be written in lowercase and tens as 'T'.
+        assert_eq!(card_index("th"), card_index("10H"));
+        assert_eq!(card_index("ks"), Some(DECK_CARDS - 1));
+        assert!(card_index("1S").is_none());
+        assert!(card_index("A♠").is_none());
+        assert!(EntropySource::Cards.collect_bits("AS KD AS").is_err());
+    }
+}
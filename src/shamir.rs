@@ -3,13 +3,18 @@
 
 use std::{
     array,
-    collections::HashMap,
     fmt::Debug,
-    ops::{Add, Mul},
+    mem,
+    ops::{Add, Div, Index, IndexMut, Mul, Sub},
+    ptr,
+    slice,
+    sync::atomic::{compiler_fence, Ordering},
 };
 
 use rand::{CryptoRng, RngCore};
 
+use crate::error::{Error, Result};
+
 pub trait Zero {
     fn zero() -> Self;
 }
@@ -18,6 +23,15 @@ pub trait Random {
     fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self;
 }
 
+/// Overwrite secret elements with zeros, in a way the compiler cannot optimize away.
+fn wipe<T: Zero>(elements: &mut [T]) {
+    for element in elements {
+        // SAFETY: The pointer is derived from a valid mutable reference.
+        unsafe { ptr::write_volatile(element, T::zero()) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
 /// A share of a secret.
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
 pub struct ShamirShare<T> {
@@ -34,7 +48,6 @@ impl<T> ShamirShare<T> {
     }
 
     /// Get the share's ID.
-    #[cfg(test)]
     pub fn id(&self) -> &u8 {
         &self.id
     }
@@ -64,27 +77,233 @@ impl<T> AsRef<ShamirShare<T>> for ShamirShare<T> {
 /// A secret sharing scheme based on Shamir's secret sharing.
 pub trait ShamirSecretSharing {
     /// Split a secret into `n` shares, of which any `t` can be used to reconstruct the secret.
-    /// Panic if `n` or `t` are zero, or if `t` is greater than `n`.
-    fn split<R: CryptoRng + RngCore>(&self, n: u8, t: u8, rng: &mut R) -> Vec<ShamirShare<Self>>
+    /// Fail if `n` or `t` are zero, or if `t` is greater than `n`. Note that with `t = 1` every
+    /// share is equal to the secret.
+    fn split<R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<Vec<ShamirShare<Self>>>
     where
         Self: Sized;
 
-    /// Reconstruct a secret from `t` shares.
-    fn reconstruct<S>(shares: &[S]) -> Self
+    /// Reconstruct a secret from `t` shares. Fail if no shares are provided, or if a share has
+    /// ID 0 or the same ID as another share.
+    fn reconstruct<S>(shares: &[S]) -> Result<Self>
     where
         S: AsRef<ShamirShare<Self>>,
         Self: Sized;
 }
 
-/// A polynomial with random coefficients and hiding a secret at its origin.
-pub struct ShamirPolynomial<T>(Vec<T>);
+/// Ensure a secret can be split into `n` shares with threshold `t`: both are non-zero and the
+/// threshold is not greater than the number of shares.
+pub fn check_parameters(n: u8, t: u8) -> Result<()> {
+    if n == 0 {
+        return Err(Error::NoShares);
+    }
+    if t == 0 {
+        return Err(Error::ZeroThreshold);
+    }
+    if t > n {
+        return Err(Error::ThresholdTooHigh {
+            threshold: t,
+            shares: n,
+        });
+    }
+    Ok(())
+}
+
+/// Ensure a set of shares can be interpolated: there is at least one share, and the shares have
+/// distinct, non-zero IDs.
+pub fn check_shares<T, S: AsRef<ShamirShare<T>>>(shares: &[S]) -> Result<()> {
+    if shares.is_empty() {
+        return Err(Error::NoShares);
+    }
+    let mut seen = [false; 256];
+    for share in shares {
+        let id = share.as_ref().id;
+        if id == 0 {
+            return Err(Error::ZeroShareId);
+        }
+        if seen[id as usize] {
+            return Err(Error::DuplicateShareId { id });
+        }
+        seen[id as usize] = true;
+    }
+    Ok(())
+}
+
+/// Compute the Lagrange basis polynomials of a set of (distinct, non-zero) share IDs at x = 0, so
+/// that the secret is the sum of the secrets of the shares weighted by these coefficients. The
+/// coefficients only depend on the IDs, so they are computed once for all the elements of a
+/// secret.
+pub fn lagrange_coefficients<T>(ids: &[u8]) -> Vec<T>
+where
+    T: From<u8> + Mul<T, Output = T> + Sub<T, Output = T> + Div<T, Output = T>,
+{
+    ids.iter()
+        .map(|xi| {
+            ids.iter()
+                .filter(|xj| *xj != xi)
+                .fold(T::from(1), |li, xj| {
+                    li * (T::from(*xj) / (T::from(*xj) - T::from(*xi)))
+                })
+        })
+        .collect()
+}
+
+/// A matrix of secret elements (stored row by row), wiped when dropped.
+struct Matrix<T: Zero> {
+    columns: usize,
+    elements: Vec<T>,
+}
+
+impl<T: Zero> Drop for Matrix<T> {
+    fn drop(&mut self) {
+        wipe(&mut self.elements);
+    }
+}
+
+impl<T: Zero> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self.elements[row * self.columns + column]
+    }
+}
+
+impl<T: Zero> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self.elements[row * self.columns + column]
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Zero + Clone + PartialEq + Sub<T, Output = T> + Mul<T, Output = T> + Div<T, Output = T>,
+{
+    /// Solve the linear system whose augmented matrix is `self` (the last column holds the
+    /// constants) by Gauss-Jordan elimination. The free variables of an underdetermined system
+    /// are set to zero. Return `None` if the system has no solution.
+    fn solve(mut self) -> Option<Vec<T>> {
+        let rows = self.elements.len() / self.columns;
+        let unknowns = self.columns - 1;
+        let mut pivots = Vec::new();
+        for column in 0..unknowns {
+            let row = pivots.len();
+            let Some(pivot_row) = (row..rows).find(|&r| self[(r, column)] != T::zero()) else {
+                continue;
+            };
+            for c in 0..self.columns {
+                self.elements
+                    .swap(row * self.columns + c, pivot_row * self.columns + c);
+            }
+
+            // Normalize the pivot row, then eliminate the column from all the other rows.
+            let pivot = self[(row, column)].clone();
+            for c in column..self.columns {
+                let value = mem::replace(&mut self[(row, c)], T::zero()) / pivot.clone();
+                self[(row, c)] = value;
+            }
+            for r in (0..rows).filter(|&r| r != row) {
+                let factor = self[(r, column)].clone();
+                if factor == T::zero() {
+                    continue;
+                }
+                for c in column..self.columns {
+                    let value = mem::replace(&mut self[(r, c)], T::zero())
+                        - factor.clone() * self[(row, c)].clone();
+                    self[(r, c)] = value;
+                }
+            }
+            pivots.push(column);
+        }
+
+        // The system is inconsistent if a row without pivot has a non-zero constant.
+        if (pivots.len()..rows).any(|r| self[(r, unknowns)] != T::zero()) {
+            return None;
+        }
+        let mut solution = vec![T::zero(); unknowns];
+        for (row, column) in pivots.into_iter().enumerate() {
+            solution[column] = self[(row, unknowns)].clone();
+        }
+        Some(solution)
+    }
+}
+
+/// Decode the polynomial of degree less than `t` passing through the points `(xs[i], ys[i])`, of
+/// which up to `e = (n - t) / 2` may be wrong, with the Berlekamp-Welch algorithm. It finds an
+/// error locator `E` (monic, of degree `e`, vanishing at the wrong points) and `Q = P * E` (of
+/// degree less than `e + t`) such that `Q(x) = y * E(x)` at every point, and returns `P = Q / E`.
+/// Return `None` if there is no such polynomial, i.e., if more than `e` points are wrong. The
+/// points must be distinct and at least `t`.
+fn berlekamp_welch<T>(xs: &[T], ys: &[T], t: usize) -> Option<ShamirPolynomial<T>>
+where
+    T: Zero + Clone + PartialEq + From<u8>,
+    T: Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + Div<T, Output = T>,
+{
+    let n = xs.len();
+    let e = (n - t) / 2;
+
+    // The unknowns are the coefficients of Q and the non-leading coefficients of E.
+    let columns = (e + t) + e + 1;
+    let mut matrix = Matrix {
+        columns,
+        elements: vec![T::zero(); n * columns],
+    };
+    // Each point gives the equation Q(x) - y * (E(x) - x^e) = y * x^e.
+    for (i, (x, y)) in xs.iter().zip(ys).enumerate() {
+        let mut power = T::from(1);
+        for j in 0..e + t {
+            matrix[(i, j)] = power.clone();
+            if j < e {
+                matrix[(i, e + t + j)] = T::zero() - y.clone() * power.clone();
+            } else if j == e {
+                matrix[(i, columns - 1)] = y.clone() * power.clone();
+            }
+            power = power * x.clone();
+        }
+    }
+    let solution = ShamirPolynomial(matrix.solve()?);
+    let (q, e_coefficients) = solution.0.split_at(e + t);
+
+    // Divide Q by E (whose leading coefficient is one); the remainder must be zero.
+    let mut remainder = ShamirPolynomial(q.to_vec());
+    let mut p = ShamirPolynomial(vec![T::zero(); t]);
+    for k in (0..t).rev() {
+        let c = remainder.0[k + e].clone();
+        for (j, ej) in e_coefficients.iter().chain([&T::from(1)]).enumerate() {
+            let value = mem::replace(&mut remainder.0[k + j], T::zero()) - c.clone() * ej.clone();
+            remainder.0[k + j] = value;
+        }
+        p.0[k] = c;
+    }
+    if remainder.0.iter().any(|r| *r != T::zero()) {
+        return None;
+    }
+    Some(p)
+}
+
+/// A polynomial with random coefficients and hiding a secret at its origin. The coefficients are
+/// wiped when the polynomial is dropped.
+pub(crate) struct ShamirPolynomial<T: Zero>(Vec<T>);
+
+impl<T: Zero> Drop for ShamirPolynomial<T> {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
 
 impl<T> ShamirPolynomial<T>
 where
-    T: Mul<T, Output = T> + Add<T, Output = T> + Clone + Zero + Random,
+    T: Mul<T, Output = T> + Add<T, Output = T> + Clone + PartialEq + Zero + Random,
 {
-    /// Generate a random polynomial of a given degree, fixing f(0) = secret.
-    pub fn random<R: CryptoRng + RngCore>(secret: T, degree: u8, rng: &mut R) -> Self {
+    /// Generate a random polynomial of degree at most `degree`, fixing f(0) = secret. All the other
+    /// coefficients (including the leading one) are drawn uniformly, so that any `degree` shares
+    /// are uniformly distributed whatever the secret. A zero leading coefficient only lowers the
+    /// degree, and `degree + 1` shares still reconstruct the secret.
+    pub(crate) fn random<R: CryptoRng + RngCore>(secret: T, degree: u8, rng: &mut R) -> Self {
         let mut f = vec![secret];
         for _ in 0..degree {
             f.push(T::random(rng));
@@ -93,7 +312,7 @@ where
     }
 
     /// Evaluate a polynomial at x using Horner's method.
-    pub fn evaluate(&self, x: T) -> T {
+    pub(crate) fn evaluate(&self, x: T) -> T {
         let mut y = T::zero();
         for c in self.0.iter().cloned().rev() {
             y = y * x.clone() + c;
@@ -102,69 +321,191 @@ where
     }
 }
 
-/// An array of field elements that can be used in Shamir's secret sharing scheme.
-#[cfg_attr(test, derive(Clone, Debug, PartialEq, Eq))]
-pub struct FieldArray<T, const N: usize>([T; N]);
-
-impl<T, const N: usize> ShamirSecretSharing for FieldArray<T, N>
+impl<T, const N: usize> ShamirPolynomial<FieldArray<T, N>>
 where
-    T: ShamirSecretSharing + Clone + Debug,
+    T: Mul<T, Output = T> + Add<T, Output = T> + Clone + PartialEq + Zero + Random,
 {
-    fn split<R: CryptoRng + RngCore>(&self, n: u8, t: u8, rng: &mut R) -> Vec<ShamirShare<Self>> {
-        let mut secrets = HashMap::new();
+    /// Generate a random polynomial of degree at most `degree` for each element of a secret array,
+    /// fixing f(0) = secret. The coefficients are drawn element by element (in the same order as
+    /// when sharing each element separately), with the same distribution as [`Self::random`].
+    pub(crate) fn random_array<R: CryptoRng + RngCore>(
+        secret: &FieldArray<T, N>,
+        degree: u8,
+        rng: &mut R,
+    ) -> Self {
+        let mut f = vec![FieldArray(array::from_fn(|i| secret.0[i].clone()))];
+        f.extend((0..degree).map(|_| FieldArray::zero()));
+        for i in 0..N {
+            for coefficient in f.iter_mut().skip(1) {
+                coefficient.0[i] = T::random(rng);
+            }
+        }
+        Self(f)
+    }
+
+    /// The coefficients of the polynomial, starting with the secret at the origin.
+    pub(crate) fn coefficients(&self) -> &[FieldArray<T, N>] {
+        &self.0
+    }
 
-        for element in &self.0 {
-            for share in element.split(n, t, rng) {
-                let (id, secret) = share.into_inner();
-                secrets.entry(id).or_insert_with(Vec::new).push(secret);
+    /// Evaluate the polynomials of all the elements at x using Horner's method.
+    pub(crate) fn evaluate_array(&self, x: &T) -> FieldArray<T, N> {
+        let mut y = FieldArray::zero();
+        for c in self.0.iter().rev() {
+            for (y, c) in y.0.iter_mut().zip(&c.0) {
+                let value = mem::replace(y, T::zero()) * x.clone() + c.clone();
+                *y = value;
             }
         }
+        y
+    }
+}
 
-        let mut shares = secrets
-            .into_iter()
-            .map(|(id, share)| {
-                let share = share
-                    .try_into()
-                    .expect("Shamir secret sharing should preserve length");
-                ShamirShare::new(id, Self(share))
-            })
-            .collect::<Vec<_>>();
+/// An array of field elements that can be used in Shamir's secret sharing scheme. The elements
+/// are wiped when the array is dropped.
+#[cfg_attr(test, derive(Clone, Debug, PartialEq, Eq))]
+pub struct FieldArray<T: Zero, const N: usize>([T; N]);
 
-        shares.sort_by(|a, b| a.id.cmp(&b.id));
-        shares
+impl<T: Zero, const N: usize> Drop for FieldArray<T, N> {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
     }
+}
 
-    fn reconstruct<S: AsRef<ShamirShare<Self>>>(shares: &[S]) -> Self {
-        Self(array::from_fn(|i| {
-            let element_shares = shares
-                .iter()
-                .map(|share| {
-                    let (id, secret) = share.as_ref().as_coordinates();
-                    ShamirShare::new(*id, secret.0[i].clone())
-                })
-                .collect::<Vec<_>>();
-            T::reconstruct(&element_shares)
-        }))
+impl<T: Zero, const N: usize> FieldArray<T, N> {
+    /// Iterate over the elements of the array.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.0.iter()
     }
 }
 
-impl<T, const N: usize> IntoIterator for FieldArray<T, N> {
-    type Item = T;
-    type IntoIter = std::array::IntoIter<Self::Item, N>;
+impl<T: Zero, const N: usize> Zero for FieldArray<T, N> {
+    fn zero() -> Self {
+        Self(array::from_fn(|_| T::zero()))
+    }
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+impl<T, const N: usize> ShamirSecretSharing for FieldArray<T, N>
+where
+    T: Zero + Random + Clone + PartialEq + Debug + From<u8>,
+    T: Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + Div<T, Output = T>,
+{
+    /// Split all the elements of the secret at once, evaluating a single polynomial with array
+    /// coefficients at the ID of each share.
+    fn split<R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<Vec<ShamirShare<Self>>> {
+        Ok(self.split_with_polynomial(n, t, rng)?.0)
+    }
+
+    /// Reconstruct all the elements of the secret at once: the Lagrange coefficients are
+    /// computed once from the share IDs, and each element is the dot product of the coefficients
+    /// with the elements of the shares.
+    fn reconstruct<S: AsRef<ShamirShare<Self>>>(shares: &[S]) -> Result<Self> {
+        check_shares(shares)?;
+        let ids = shares.iter().map(|s| s.as_ref().id).collect::<Vec<_>>();
+        let coefficients = lagrange_coefficients::<T>(&ids);
+
+        let mut secret = Self::zero();
+        for (li, share) in coefficients.iter().zip(shares) {
+            for (y, yi) in secret.0.iter_mut().zip(&share.as_ref().secret.0) {
+                let sum = mem::replace(y, T::zero()) + li.clone() * yi.clone();
+                *y = sum;
+            }
+        }
+        Ok(secret)
+    }
+}
+
+impl<T, const N: usize> FieldArray<T, N>
+where
+    T: Zero + Random + Clone + PartialEq + Debug + From<u8>,
+    T: Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + Div<T, Output = T>,
+{
+    /// Split the secret like [`ShamirSecretSharing::split`], and also return the polynomial the
+    /// shares lie on (e.g., to commit to it).
+    pub(crate) fn split_with_polynomial<R: CryptoRng + RngCore>(
+        &self,
+        n: u8,
+        t: u8,
+        rng: &mut R,
+    ) -> Result<(Vec<ShamirShare<Self>>, ShamirPolynomial<Self>)> {
+        check_parameters(n, t)?;
+        let polynomial = ShamirPolynomial::random_array(self, t - 1, rng);
+
+        let shares = (1..=n)
+            .map(|id| ShamirShare::new(id, polynomial.evaluate_array(&T::from(id))))
+            .collect();
+        Ok((shares, polynomial))
+    }
+
+    /// Reconstruct a secret split with threshold `t` from `n` shares of which up to
+    /// `(n - t) / 2` may be corrupted, and return the (sorted) IDs of the shares disagreeing with
+    /// the secret. Each element is decoded separately with the Berlekamp-Welch algorithm, so a
+    /// share is corrupted if any of its elements is. Fail if fewer than `t` shares are provided,
+    /// if the share IDs are not distinct and non-zero, or if too many shares are corrupted (which
+    /// is only guaranteed to be detected with at most `n - t - (n - t) / 2` corrupted shares).
+    /// Unlike [`Self::reconstruct`], the decoding branches on the values of the shares.
+    pub fn reconstruct_with_errors<S: AsRef<ShamirShare<Self>>>(
+        shares: &[S],
+        t: u8,
+    ) -> Result<(Self, Vec<u8>)> {
+        check_shares(shares)?;
+        let t = t as usize;
+        if t == 0 {
+            return Err(Error::ZeroThreshold);
+        }
+        if shares.len() < t {
+            return Err(Error::NotEnoughShares {
+                expected: t,
+                actual: shares.len(),
+            });
+        }
+        let max = (shares.len() - t) / 2;
+        let ids = shares.iter().map(|s| s.as_ref().id).collect::<Vec<_>>();
+        let xs = ids.iter().map(|id| T::from(*id)).collect::<Vec<_>>();
+
+        let mut secret = Self::zero();
+        let mut corrupted = [false; 256];
+        let mut ys = vec![T::zero(); shares.len()];
+        for (i, element) in secret.0.iter_mut().enumerate() {
+            for (y, share) in ys.iter_mut().zip(shares) {
+                *y = share.as_ref().secret.0[i].clone();
+            }
+            let Some(polynomial) = berlekamp_welch(&xs, &ys, t) else {
+                wipe(&mut ys);
+                return Err(Error::TooManyCorruptedShares { max });
+            };
+            for ((id, x), y) in ids.iter().zip(&xs).zip(&ys) {
+                if polynomial.evaluate(x.clone()) != *y {
+                    corrupted[*id as usize] = true;
+                }
+            }
+            *element = polynomial.0[0].clone();
+        }
+        wipe(&mut ys);
+
+        let corrupted = (1..=u8::MAX)
+            .filter(|id| corrupted[*id as usize])
+            .collect::<Vec<_>>();
+        if corrupted.len() > max {
+            return Err(Error::TooManyCorruptedShares { max });
+        }
+        Ok((secret, corrupted))
     }
 }
 
-impl<T, const N: usize> From<[T; N]> for FieldArray<T, N> {
+impl<T: Zero, const N: usize> From<[T; N]> for FieldArray<T, N> {
     fn from(value: [T; N]) -> Self {
         Self(value)
     }
 }
 
 #[cfg(test)]
-impl<T: Random, const N: usize> Random for FieldArray<T, N> {
+impl<T: Random + Zero, const N: usize> Random for FieldArray<T, N> {
     fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
         Self(array::from_fn(|_| T::random(rng)))
     }
@@ -174,9 +515,15 @@ impl<T: Random, const N: usize> Random for FieldArray<T, N> {
 pub mod test {
     use std::fmt::Debug;
 
-    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use rand::{
+        rngs::StdRng,
+        seq::{index::sample, SliceRandom},
+        Rng,
+        SeedableRng,
+    };
 
-    use super::{FieldArray, Random, ShamirSecretSharing};
+    use super::{lagrange_coefficients, FieldArray, Random, ShamirSecretSharing, ShamirShare};
+    use crate::{error::Error, gf256::Gf256};
 
     pub fn test_reconstruct<T>() -> T
     where
@@ -187,9 +534,9 @@ pub mod test {
 
         let n = 5;
         let t = 3;
-        let shares = secret.clone().split(n, t, &mut rng);
+        let shares = secret.clone().split(n, t, &mut rng).unwrap();
 
-        let reconstructed = T::reconstruct(&shares[..t as usize]);
+        let reconstructed = T::reconstruct(&shares[..t as usize]).unwrap();
         assert_eq!(secret, reconstructed);
         secret
     }
@@ -200,12 +547,12 @@ pub mod test {
     {
         let mut rng = StdRng::seed_from_u64(0);
         let secret = T::random(&mut rng);
-        let mut shares = secret.clone().split(5, 3, &mut rng);
+        let mut shares = secret.clone().split(5, 3, &mut rng).unwrap();
         let share_4 = shares.pop().unwrap();
         let _share_3 = shares.pop().unwrap();
         let share_2 = shares.pop().unwrap();
         let share_1 = shares.pop().unwrap();
-        let reconstructed = T::reconstruct(&[share_1, share_2, share_4]);
+        let reconstructed = T::reconstruct(&[share_1, share_2, share_4]).unwrap();
         assert_eq!(secret, reconstructed);
         secret
     }
@@ -219,13 +566,64 @@ pub mod test {
 
         let n = 5;
         let t = 3;
-        let shares = secret.clone().split(n, t, &mut rng);
-        let reconstructed = T::reconstruct(&shares[0..(t - 1) as usize]);
+        let shares = secret.clone().split(n, t, &mut rng).unwrap();
+        let reconstructed = T::reconstruct(&shares[0..(t - 1) as usize]).unwrap();
 
         assert_ne!(secret, reconstructed);
         (secret, reconstructed)
     }
 
+    pub fn test_reconstruct_invalid_shares<T>()
+    where
+        T: ShamirSecretSharing + Random + PartialEq + Eq + Debug + Clone,
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let secret = T::random(&mut rng);
+        let shares = secret.split(5, 3, &mut rng).unwrap();
+
+        // No shares.
+        let empty: &[ShamirShare<T>] = &[];
+        assert!(matches!(T::reconstruct(empty), Err(Error::NoShares)));
+
+        // The same share twice.
+        let duplicate = [&shares[0], &shares[1], &shares[1]];
+        assert!(matches!(
+            T::reconstruct(&duplicate),
+            Err(Error::DuplicateShareId { id: 2 })
+        ));
+
+        // A share with ID 0 (the secret itself).
+        let zero = ShamirShare::new(0, shares[0].secret().clone());
+        assert!(matches!(
+            T::reconstruct(&[&zero, &shares[1], &shares[2]]),
+            Err(Error::ZeroShareId)
+        ));
+    }
+
+    pub fn test_split_invalid_parameters<T>()
+    where
+        T: ShamirSecretSharing + Random + PartialEq + Eq + Debug + Clone,
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let secret = T::random(&mut rng);
+        assert!(matches!(secret.split(0, 0, &mut rng), Err(Error::NoShares)));
+        assert!(matches!(
+            secret.split(3, 0, &mut rng),
+            Err(Error::ZeroThreshold)
+        ));
+        assert!(matches!(
+            secret.split(2, 3, &mut rng),
+            Err(Error::ThresholdTooHigh {
+                threshold: 3,
+                shares: 2
+            })
+        ));
+
+        // With a threshold of one, every share is the secret itself.
+        let shares = secret.split(3, 1, &mut rng).unwrap();
+        assert!(shares.iter().all(|share| share.secret() == &secret));
+    }
+
     pub fn chaos_test<T>()
     where
         T: ShamirSecretSharing + Random + PartialEq + Eq + Debug + Clone,
@@ -235,11 +633,11 @@ pub mod test {
             for t in 1..=n {
                 let secret = T::random(&mut rng);
 
-                let mut shares = secret.clone().split(n, t, &mut rng);
+                let mut shares = secret.clone().split(n, t, &mut rng).unwrap();
                 shares.shuffle(&mut rng);
 
                 for i in 1..=t {
-                    let reconstructed = T::reconstruct(&shares[0..i as usize]);
+                    let reconstructed = T::reconstruct(&shares[0..i as usize]).unwrap();
                     if i == t {
                         assert_eq!(secret, reconstructed);
                     } else {
@@ -252,37 +650,173 @@ pub mod test {
 
     #[test]
     fn reconstruct() {
-        test_reconstruct::<FieldArray<gf256::gf256, 16>>();
-        test_reconstruct::<FieldArray<gf256::gf256, 20>>();
-        test_reconstruct::<FieldArray<gf256::gf256, 24>>();
-        test_reconstruct::<FieldArray<gf256::gf256, 28>>();
-        test_reconstruct::<FieldArray<gf256::gf256, 32>>();
+        test_reconstruct::<FieldArray<Gf256, 16>>();
+        test_reconstruct::<FieldArray<Gf256, 20>>();
+        test_reconstruct::<FieldArray<Gf256, 24>>();
+        test_reconstruct::<FieldArray<Gf256, 28>>();
+        test_reconstruct::<FieldArray<Gf256, 32>>();
     }
 
     #[test]
     fn reconstruct_sparse() {
-        test_reconstruct_sparse::<FieldArray<gf256::gf256, 16>>();
-        test_reconstruct_sparse::<FieldArray<gf256::gf256, 20>>();
-        test_reconstruct_sparse::<FieldArray<gf256::gf256, 28>>();
-        test_reconstruct_sparse::<FieldArray<gf256::gf256, 24>>();
-        test_reconstruct_sparse::<FieldArray<gf256::gf256, 32>>();
+        test_reconstruct_sparse::<FieldArray<Gf256, 16>>();
+        test_reconstruct_sparse::<FieldArray<Gf256, 20>>();
+        test_reconstruct_sparse::<FieldArray<Gf256, 28>>();
+        test_reconstruct_sparse::<FieldArray<Gf256, 24>>();
+        test_reconstruct_sparse::<FieldArray<Gf256, 32>>();
     }
 
     #[test]
     fn reconstruct_missing_shares() {
-        test_reconstruct_missing_shares::<FieldArray<gf256::gf256, 16>>();
-        test_reconstruct_missing_shares::<FieldArray<gf256::gf256, 20>>();
-        test_reconstruct_missing_shares::<FieldArray<gf256::gf256, 24>>();
-        test_reconstruct_missing_shares::<FieldArray<gf256::gf256, 28>>();
-        test_reconstruct_missing_shares::<FieldArray<gf256::gf256, 32>>();
+        test_reconstruct_missing_shares::<FieldArray<Gf256, 16>>();
+        test_reconstruct_missing_shares::<FieldArray<Gf256, 20>>();
+        test_reconstruct_missing_shares::<FieldArray<Gf256, 24>>();
+        test_reconstruct_missing_shares::<FieldArray<Gf256, 28>>();
+        test_reconstruct_missing_shares::<FieldArray<Gf256, 32>>();
+    }
+
+    #[test]
+    fn lagrange() {
+        // The coefficients interpolate the constant polynomial 1, so they sum to 1.
+        for ids in [
+            vec![1],
+            vec![1, 2, 3],
+            vec![5, 2, 255, 17],
+            (1..=255).collect(),
+        ] {
+            let coefficients = lagrange_coefficients::<Gf256>(&ids);
+            assert_eq!(coefficients.len(), ids.len());
+            let sum = coefficients.into_iter().fold(Gf256(0), |sum, li| sum + li);
+            assert_eq!(sum, Gf256(1));
+        }
+    }
+
+    #[test]
+    fn split_matches_elements() {
+        // Splitting an array gives the same shares as splitting each element separately with the
+        // same randomness.
+        for (n, t) in [(1, 1), (3, 2), (5, 3), (16, 16), (255, 10)] {
+            let secret = FieldArray::<Gf256, 32>::random(&mut StdRng::seed_from_u64(1));
+
+            let mut rng = StdRng::seed_from_u64(0);
+            let shares = secret.split(n, t, &mut rng).unwrap();
+
+            let mut rng = StdRng::seed_from_u64(0);
+            let elements = secret
+                .iter()
+                .map(|element| element.split(n, t, &mut rng).unwrap())
+                .collect::<Vec<_>>();
+
+            assert_eq!(shares.len(), n as usize);
+            for (i, share) in shares.iter().enumerate() {
+                assert_eq!(*share.id(), i as u8 + 1);
+                let expected = elements.iter().map(|e| *e[i].secret()).collect::<Vec<_>>();
+                assert_eq!(share.secret().iter().copied().collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn split_invalid_parameters() {
+        test_split_invalid_parameters::<FieldArray<Gf256, 16>>();
+        test_split_invalid_parameters::<FieldArray<Gf256, 32>>();
+    }
+
+    #[test]
+    fn reconstruct_invalid_shares() {
+        test_reconstruct_invalid_shares::<FieldArray<Gf256, 16>>();
+        test_reconstruct_invalid_shares::<FieldArray<Gf256, 32>>();
     }
 
     #[test]
     fn chaos() {
-        chaos_test::<FieldArray<gf256::gf256, 16>>();
-        chaos_test::<FieldArray<gf256::gf256, 20>>();
-        chaos_test::<FieldArray<gf256::gf256, 24>>();
-        chaos_test::<FieldArray<gf256::gf256, 28>>();
-        chaos_test::<FieldArray<gf256::gf256, 32>>();
+        chaos_test::<FieldArray<Gf256, 16>>();
+        chaos_test::<FieldArray<Gf256, 20>>();
+        chaos_test::<FieldArray<Gf256, 24>>();
+        chaos_test::<FieldArray<Gf256, 28>>();
+        chaos_test::<FieldArray<Gf256, 32>>();
+    }
+
+    /// Split a random secret into `n` shares with threshold `t`, then corrupt the shares at the
+    /// given positions by adding a random non-zero value to one or more (distinct) elements.
+    fn corrupted_shares(
+        n: u8,
+        t: u8,
+        corrupted: &[usize],
+        rng: &mut StdRng,
+    ) -> (
+        FieldArray<Gf256, 32>,
+        Vec<ShamirShare<FieldArray<Gf256, 32>>>,
+    ) {
+        let secret = FieldArray::<Gf256, 32>::random(rng);
+        let mut shares = secret.split(n, t, rng).unwrap();
+        for &i in corrupted {
+            let count = rng.random_range(1..=32);
+            for j in sample(rng, 32, count) {
+                shares[i].secret.0[j] += Gf256(rng.random_range(1..=u8::MAX));
+            }
+        }
+        (secret, shares)
+    }
+
+    #[test]
+    fn reconstruct_with_errors() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (n, t) in [(3, 3), (4, 3), (5, 3), (7, 3), (10, 4), (20, 1), (40, 20)] {
+            let max = (n - t) as usize / 2;
+            for errors in 0..=max.min(8) {
+                let mut positions = (0..n as usize).collect::<Vec<_>>();
+                positions.shuffle(&mut rng);
+                positions.truncate(errors);
+                let (secret, mut shares) = corrupted_shares(n, t, &positions, &mut rng);
+
+                let mut expected = positions.iter().map(|i| *i as u8 + 1).collect::<Vec<_>>();
+                expected.sort();
+                shares.shuffle(&mut rng);
+                let (reconstructed, corrupted) =
+                    FieldArray::reconstruct_with_errors(&shares, t).unwrap();
+                assert_eq!(reconstructed, secret);
+                assert_eq!(corrupted, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reconstruct_with_too_many_errors() {
+        // One more corrupted share than can be corrected is detected when `n - t` is odd.
+        let mut rng = StdRng::seed_from_u64(0);
+        for (n, t, corrupted) in [
+            (4, 3, vec![0]),
+            (6, 3, vec![1, 4]),
+            (8, 2, vec![0, 1, 7, 3]),
+        ] {
+            let (_, shares) = corrupted_shares(n, t, &corrupted, &mut rng);
+            let max = corrupted.len() - 1;
+            assert!(matches!(
+                FieldArray::reconstruct_with_errors(&shares, t),
+                Err(Error::TooManyCorruptedShares { max: m }) if m == max
+            ));
+        }
+    }
+
+    #[test]
+    fn reconstruct_with_errors_invalid_shares() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (_, shares) = corrupted_shares(5, 3, &[], &mut rng);
+        assert!(matches!(
+            FieldArray::reconstruct_with_errors(&shares, 0),
+            Err(Error::ZeroThreshold)
+        ));
+        assert!(matches!(
+            FieldArray::reconstruct_with_errors(&shares[..2], 3),
+            Err(Error::NotEnoughShares {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            FieldArray::reconstruct_with_errors(&[&shares[0], &shares[1], &shares[1]], 2),
+            Err(Error::DuplicateShareId { id: 2 })
+        ));
     }
 }
@@ -1,6 +1,26 @@
 // Copyright (c) Alberto Sonnino
 // SPDX-License-Identifier: Apache-2.0
 
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+/// NFKD-normalize a string into a buffer wiped when dropped. The buffer is grown by copying into
+/// a larger (wiped) buffer rather than reallocated, so that no unwiped copy of the string is left
+/// on the heap.
+pub fn normalize(s: &str) -> Zeroizing<String> {
+    let mut normalized = Zeroizing::new(String::with_capacity(s.len()));
+    for c in s.nfkd() {
+        if normalized.len() + c.len_utf8() > normalized.capacity() {
+            let capacity = 2 * normalized.capacity() + c.len_utf8();
+            let mut larger = Zeroizing::new(String::with_capacity(capacity));
+            larger.push_str(&normalized);
+            normalized = larger;
+        }
+        normalized.push(c);
+    }
+    normalized
+}
+
 /// Convert an iterator of bytes into a vector of bits.
 pub fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
     bytes
@@ -21,6 +41,61 @@ pub fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
         .collect()
 }
 
+/// Check whether two characters are neighbouring keys on a QWERTY keyboard.
+fn adjacent_keys(a: char, b: char) -> bool {
+    const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let position = |c: char| {
+        ROWS.iter().enumerate().find_map(|(row, keys)| {
+            keys.find(c.to_ascii_lowercase())
+                .map(|column| (row as isize, column as isize))
+        })
+    };
+
+    // Each row is shifted to the right with respect to the row above it.
+    match (position(a), position(b)) {
+        (Some((row_a, column_a)), Some((row_b, column_b))) => match row_b - row_a {
+            0 => (column_a - column_b).abs() == 1,
+            1 => column_b == column_a || column_b == column_a - 1,
+            -1 => column_a == column_b || column_a == column_b - 1,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Compute the edit distance between two strings, in half-edits. Insertions, deletions, and
+/// transpositions of two consecutive characters cost two; substitutions cost two, or one if the
+/// characters are neighbouring keys on a QWERTY keyboard.
+pub fn typo_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = 2 * i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = 2 * j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = match (a[i - 1], b[j - 1]) {
+                (x, y) if x == y => 0,
+                (x, y) if adjacent_keys(x, y) => 1,
+                _ => 2,
+            };
+            d[i][j] = (d[i - 1][j] + 2)
+                .min(d[i][j - 1] + 2)
+                .min(d[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 2);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -54,4 +129,26 @@ mod test {
             vec![0b1010_1010]
         );
     }
+
+    #[test]
+    fn test_adjacent_keys() {
+        assert!(adjacent_keys('q', 'w'));
+        assert!(adjacent_keys('s', 'w'));
+        assert!(adjacent_keys('s', 'x'));
+        assert!(adjacent_keys('b', 'g'));
+        assert!(!adjacent_keys('q', 'e'));
+        assert!(!adjacent_keys('a', 'x'));
+        assert!(!adjacent_keys('a', 'é'));
+    }
+
+    #[test]
+    fn test_typo_distance() {
+        assert_eq!(typo_distance("abandon", "abandon"), 0);
+        assert_eq!(typo_distance("abandon", "abamdon"), 1);
+        assert_eq!(typo_distance("abandon", "abaxdon"), 2);
+        assert_eq!(typo_distance("abandon", "abnadon"), 2);
+        assert_eq!(typo_distance("abandon", "abandn"), 2);
+        assert_eq!(typo_distance("abandon", "abandoned"), 4);
+        assert_eq!(typo_distance("", "zoo"), 6);
+    }
 }
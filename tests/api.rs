@@ -0,0 +1,208 @@
+// Copyright (c) Alberto Sonnino
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use shamir_bip39::{
+    convert_mnemonic,
+    Bip39Dictionary,
+    Bip39Secret,
+    Bip39Share,
+    EntropySource,
+    Error,
+    Language,
+    PolynomialCommitment,
+    ShareProof,
+};
+
+const MNEMONIC: &str = "permit universe parent weapon amused modify essay borrow tobacco budget \
+    walnut lunch consider gallery ride amazing frog forget treat market chapter velvet useless \
+    topple";
+
+fn english() -> Bip39Dictionary {
+    Bip39Dictionary::builtin(Language::English).unwrap()
+}
+
+#[test]
+fn split_and_reconstruct() {
+    let dictionary = english();
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut shares = shamir_bip39::split(MNEMONIC, 5, 3, &dictionary, &mut rng).unwrap();
+    assert_eq!(shares.len(), 5);
+
+    // Any 3 shares reconstruct the secret.
+    shares.shuffle(&mut rng);
+    let secret = shamir_bip39::reconstruct(&shares[..3]).unwrap();
+    assert_eq!(secret.to_mnemonic(&dictionary), MNEMONIC);
+
+    // Fewer shares reconstruct a different (but valid) secret.
+    let decoy = shamir_bip39::reconstruct(&shares[..2]).unwrap();
+    assert_ne!(decoy.to_mnemonic(&dictionary), MNEMONIC);
+    assert!(decoy.is_valid().is_ok());
+}
+
+#[test]
+fn shares_are_mnemonics() {
+    let dictionary = english();
+    let mut rng = StdRng::seed_from_u64(1);
+    let shares = shamir_bip39::split(MNEMONIC, 3, 2, &dictionary, &mut rng).unwrap();
+
+    // Each share can be written down and read back as a bip-39 mnemonic.
+    let parsed = shares
+        .iter()
+        .map(|share| {
+            let mnemonic = share.to_mnemonic(&dictionary);
+            shamir_bip39::check(&mnemonic, &dictionary).unwrap();
+            Bip39Share::from_mnemonic(*share.id(), &mnemonic, &dictionary).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let secret = shamir_bip39::reconstruct(&parsed[1..]).unwrap();
+    assert_eq!(secret.to_mnemonic(&dictionary), MNEMONIC);
+}
+
+#[test]
+fn invalid_inputs() {
+    let dictionary = english();
+    let mut rng = StdRng::seed_from_u64(2);
+
+    // Invalid parameters.
+    assert!(shamir_bip39::split(MNEMONIC, 0, 0, &dictionary, &mut rng).is_err());
+    assert!(shamir_bip39::split(MNEMONIC, 2, 3, &dictionary, &mut rng).is_err());
+
+    // Invalid mnemonics.
+    let short = MNEMONIC.rsplit_once(' ').unwrap().0;
+    assert!(matches!(
+        shamir_bip39::split(short, 3, 2, &dictionary, &mut rng),
+        Err(Error::InvalidMnemonicLength { actual: 23, .. })
+    ));
+    let checksum = MNEMONIC.replace("topple", "abandon");
+    assert!(matches!(
+        shamir_bip39::check(&checksum, &dictionary),
+        Err(Error::InvalidChecksum)
+    ));
+    let misspelled = MNEMONIC.replace("weapon", "weapom");
+    assert!(matches!(
+        shamir_bip39::check(&misspelled, &dictionary),
+        Err(Error::InvalidWord { index: Some(3), .. })
+    ));
+
+    // No shares.
+    assert!(matches!(
+        shamir_bip39::reconstruct(&[]),
+        Err(Error::NoShares)
+    ));
+
+    // The same share twice.
+    let shares = shamir_bip39::split(MNEMONIC, 3, 2, &dictionary, &mut rng).unwrap();
+    let mnemonic = shares[0].to_mnemonic(&dictionary);
+    let copy = Bip39Share::from_mnemonic(*shares[0].id(), &mnemonic, &dictionary).unwrap();
+    assert!(matches!(
+        shamir_bip39::reconstruct(&[copy, shares.into_iter().next().unwrap()]),
+        Err(Error::DuplicateShareId { id: 1 })
+    ));
+}
+
+#[test]
+fn languages() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let english = english();
+    for language in [Language::Japanese, Language::Spanish, Language::Czech] {
+        let dictionary = Bip39Dictionary::builtin(language).unwrap();
+        let mnemonic = convert_mnemonic(MNEMONIC, &english, &dictionary).unwrap();
+        assert_eq!(Language::detect([mnemonic.as_str()]).unwrap(), language);
+
+        let shares = shamir_bip39::split(&mnemonic, 3, 2, &dictionary, &mut rng).unwrap();
+        let secret = shamir_bip39::reconstruct(&shares[..2]).unwrap();
+        assert_eq!(secret.to_mnemonic(&english), MNEMONIC);
+    }
+}
+
+#[test]
+fn generate() {
+    let dictionary = english();
+    let mut rng = StdRng::seed_from_u64(4);
+    let secret = Bip39Secret::generate(12, &mut rng).unwrap();
+    let mnemonic = secret.to_mnemonic(&dictionary);
+    assert_eq!(mnemonic.split_whitespace().count(), 12);
+    shamir_bip39::check(&mnemonic, &dictionary).unwrap();
+
+    // From physical randomness.
+    let flips = "HT".repeat(64);
+    let bits = EntropySource::Coin.collect_bits(&flips).unwrap();
+    let secret = Bip39Secret::from_entropy_bits(&bits, 12).unwrap();
+    assert!(secret.is_valid().is_ok());
+}
+
+#[test]
+fn commitments() {
+    let dictionary = english();
+    let mut rng = StdRng::seed_from_u64(5);
+    let (shares, commitment, proofs) =
+        shamir_bip39::split_verifiable(MNEMONIC, 3, 2, &dictionary, &mut rng).unwrap();
+
+    // The commitment is published as text, and each proof is given as text to its shareholder.
+    let commitment = commitment
+        .to_string()
+        .parse::<PolynomialCommitment>()
+        .unwrap();
+    let proofs = proofs
+        .iter()
+        .map(|proof| proof.to_string().parse::<ShareProof>().unwrap())
+        .collect::<Vec<_>>();
+
+    // Each share, read back from its mnemonic, lies on the committed polynomial.
+    for share in &shares {
+        let mnemonic = share.to_mnemonic(&dictionary);
+        let share = Bip39Share::from_mnemonic(*share.id(), &mnemonic, &dictionary).unwrap();
+        shamir_bip39::verify_share(&share, &commitment, &proofs).unwrap();
+    }
+
+    // A share written down with the wrong ID does not.
+    let mnemonic = shares[0].to_mnemonic(&dictionary);
+    let share = Bip39Share::from_mnemonic(2, &mnemonic, &dictionary).unwrap();
+    assert!(matches!(
+        shamir_bip39::verify_share(&share, &commitment, &proofs),
+        Err(Error::CommitmentMismatch { id: 2 })
+    ));
+    assert!(matches!(
+        commitment.verify_share(&share, &proofs[0]),
+        Err(Error::ShareIdMismatch { share: 2, proof: 1 })
+    ));
+
+    // The shares still reconstruct the secret.
+    let secret = shamir_bip39::reconstruct(&shares[1..]).unwrap();
+    assert_eq!(secret.to_mnemonic(&dictionary), MNEMONIC);
+}
+
+#[test]
+fn reconstruct_with_errors() {
+    let dictionary = english();
+    let mut rng = StdRng::seed_from_u64(6);
+    let shares = shamir_bip39::split(MNEMONIC, 6, 3, &dictionary, &mut rng).unwrap();
+    let mut mnemonics = shares
+        .iter()
+        .map(|share| share.to_mnemonic(&dictionary))
+        .collect::<Vec<_>>();
+    let parse = |mnemonics: &[String]| {
+        mnemonics
+            .iter()
+            .enumerate()
+            .map(|(i, m)| Bip39Share::from_mnemonic(i as u8 + 1, m, &dictionary).unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    // A mistyped word (likely making the checksum invalid) is corrected and reported.
+    let (first, rest) = mnemonics[1].split_once(' ').unwrap();
+    let other = if first == "zoo" { "zone" } else { "zoo" };
+    mnemonics[1] = format!("{other} {rest}");
+    let (secret, corrupted) = shamir_bip39::reconstruct_with_errors(&parse(&mnemonics), 3).unwrap();
+    assert_eq!(secret.to_mnemonic(&dictionary), MNEMONIC);
+    assert_eq!(corrupted, [2]);
+
+    // A second corrupted (mixed-up) share is detected but cannot be corrected.
+    mnemonics[4] = mnemonics[5].clone();
+    assert!(matches!(
+        shamir_bip39::reconstruct_with_errors(&parse(&mnemonics), 3),
+        Err(Error::TooManyCorruptedShares { max: 1 })
+    ));
+}